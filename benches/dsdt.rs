@@ -0,0 +1,155 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+//! Serialization benchmark for a large, deeply nested DSDT.
+//!
+//! The streaming serialization is compared with a baseline where every
+//! method and device is first serialized into a temporary buffer and
+//! then copied into the sink, as containers used to do to compute
+//! their PkgLength.
+//!
+//! Run with `cargo bench --bench dsdt`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use acpi_tables::aml::*;
+use acpi_tables::{Aml, AmlSink};
+
+const NUM_CPUS: u32 = 512;
+const NUM_PCI_SLOTS: u32 = 32;
+const ITERATIONS: u32 = 1000;
+
+/// Serializes the wrapped object into a temporary buffer before copying
+/// it to the sink.
+struct Buffered<'a>(&'a dyn Aml);
+
+impl Aml for Buffered<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let mut bytes = Vec::new();
+        self.0.to_aml_bytes(&mut bytes);
+        sink.vec(&bytes);
+    }
+
+    fn aml_len(&self) -> usize {
+        self.0.aml_len()
+    }
+}
+
+/// Build a DSDT-like hierarchy and time `iterations` serializations of it,
+/// returning the size of the bytecode and the total time spent. With
+/// `buffered`, every container is serialized through a temporary buffer.
+fn serialize_dsdt(iterations: u32, buffered: bool) -> (usize, Duration) {
+    let uids: Vec<u32> = (0..NUM_CPUS).collect();
+    let names: Vec<String> = (0..NUM_CPUS).map(|i| format!("C{i:03X}")).collect();
+    let mats: Vec<BufferData> = (0..NUM_CPUS)
+        .map(|i| BufferData::new(vec![0, 8, i as u8, i as u8, 1, 0, 0, 0]))
+        .collect();
+
+    let sta_call = MethodCall::new("CSTA".into(), vec![&Local(0)]);
+    let sta_ret = Return::new(&sta_call);
+    let ej0_call = MethodCall::new("CEJ0".into(), vec![&Local(0)]);
+    let hid = Name::new("_HID".into(), &"ACPI0007");
+
+    let mut uid_names = Vec::new();
+    let mut mat_names = Vec::new();
+    for i in 0..NUM_CPUS as usize {
+        uid_names.push(Name::new("_UID".into(), &uids[i]));
+        mat_names.push(Name::new("_MAT".into(), &mats[i]));
+    }
+    let stores: Vec<Store> = uids.iter().map(|uid| Store::new(&Local(0), uid)).collect();
+
+    let mut sta_methods = Vec::new();
+    let mut ej0_methods = Vec::new();
+    for store in &stores {
+        sta_methods.push(Method::new("_STA".into(), 0, false, vec![store, &sta_ret]));
+        ej0_methods.push(Method::new("_EJ0".into(), 1, false, vec![store, &ej0_call]));
+    }
+
+    let buffered_sta: Vec<Buffered> = sta_methods.iter().map(|m| Buffered(m)).collect();
+    let buffered_ej0: Vec<Buffered> = ej0_methods.iter().map(|m| Buffered(m)).collect();
+    let cpus: Vec<Device> = (0..NUM_CPUS as usize)
+        .map(|i| {
+            let (sta, ej0): (&dyn Aml, &dyn Aml) = if buffered {
+                (&buffered_sta[i], &buffered_ej0[i])
+            } else {
+                (&sta_methods[i], &ej0_methods[i])
+            };
+            Device::new(
+                names[i].as_str().into(),
+                vec![&hid, &uid_names[i], &mat_names[i], sta, ej0],
+            )
+        })
+        .collect();
+    let buffered_cpus: Vec<Buffered> = cpus.iter().map(|cpu| Buffered(cpu)).collect();
+    let mut cpu_children: Vec<&dyn Aml> = Vec::new();
+    let cpu_hid = Name::new("_HID".into(), &"ACPI0010");
+    cpu_children.push(&cpu_hid);
+    for i in 0..cpus.len() {
+        cpu_children.push(if buffered {
+            &buffered_cpus[i]
+        } else {
+            &cpus[i]
+        });
+    }
+    let cpus_container = Device::new("CPUS".into(), cpu_children);
+
+    let slot_names: Vec<String> = (0..NUM_PCI_SLOTS).map(|i| format!("S{i:03}")).collect();
+    let adrs: Vec<u32> = (0..NUM_PCI_SLOTS).map(|i| i << 16).collect();
+    let adr_names: Vec<Name> = adrs.iter().map(|a| Name::new("_ADR".into(), a)).collect();
+    let slots: Vec<Device> = (0..NUM_PCI_SLOTS as usize)
+        .map(|i| Device::new(slot_names[i].as_str().into(), vec![&adr_names[i]]))
+        .collect();
+    let buffered_slots: Vec<Buffered> = slots.iter().map(|slot| Buffered(slot)).collect();
+    let mut pci_children: Vec<&dyn Aml> = Vec::new();
+    let pci_hid = Name::new("_HID".into(), &EISAName::new("PNP0A08"));
+    pci_children.push(&pci_hid);
+    for i in 0..slots.len() {
+        pci_children.push(if buffered {
+            &buffered_slots[i]
+        } else {
+            &slots[i]
+        });
+    }
+    let pci = Device::new("PCI0".into(), pci_children);
+
+    let (buffered_cpus_container, buffered_pci) = (Buffered(&cpus_container), Buffered(&pci));
+    let sb_children: Vec<&dyn Aml> = if buffered {
+        vec![&buffered_cpus_container, &buffered_pci]
+    } else {
+        vec![&cpus_container, &pci]
+    };
+    let sb = Scope::new("\\_SB_".into(), sb_children);
+    let buffered_sb = Buffered(&sb);
+    let root: &dyn Aml = if buffered { &buffered_sb } else { &sb };
+
+    let mut len = 0;
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let mut bytes = Vec::new();
+        root.to_aml_bytes(&mut bytes);
+        len = black_box(bytes).len();
+    }
+
+    (len, start.elapsed())
+}
+
+fn main() {
+    for (label, buffered) in [("buffered", true), ("streaming", false)] {
+        // Warm up
+        serialize_dsdt(ITERATIONS / 10, buffered);
+
+        let (len, total) = serialize_dsdt(ITERATIONS, buffered);
+
+        println!(
+            "dsdt ({}): {} CPUs, {} PCI slots, {} bytes: {:?}/iter",
+            label,
+            NUM_CPUS,
+            NUM_PCI_SLOTS,
+            len,
+            total / ITERATIONS
+        );
+    }
+}
@@ -8,7 +8,7 @@ extern crate alloc;
 
 use crate::{gas, Aml, AmlSink};
 use alloc::string::String;
-use alloc::{vec, vec::Vec};
+use alloc::vec::Vec;
 
 // AML byte stream defines
 const ZEROOP: u8 = 0x00;
@@ -121,6 +121,7 @@ impl Aml for Ones {
 
 /// Represents Namestring to construct ACPI objects like
 /// Name/Device/Method/Scope and so on...
+#[derive(Clone)]
 pub struct Path {
     root: bool,
     name_parts: Vec<[u8; 4]>,
@@ -144,10 +145,19 @@ impl Aml for Path {
             }
         };
 
-        for part in self.name_parts.clone().iter_mut() {
+        for part in &self.name_parts {
             sink.vec(part);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        let prefix_len = match self.name_parts.len() {
+            0 | 1 => 0,
+            2 => 1,
+            _ => 2,
+        };
+        self.root as usize + prefix_len + 4 * self.name_parts.len()
+    }
 }
 
 impl Path {
@@ -237,6 +247,10 @@ impl Aml for Name {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
         sink.vec(&self.bytes);
     }
+
+    fn aml_len(&self) -> usize {
+        self.bytes.len()
+    }
 }
 
 impl Name {
@@ -245,7 +259,8 @@ impl Name {
     /// * `path` - The namestring.
     /// * `inner` - AML objects contained in this namespace.
     pub fn new(path: Path, inner: &dyn Aml) -> Self {
-        let mut bytes = vec![NAMEOP];
+        let mut bytes = Vec::with_capacity(1 + path.aml_len() + inner.aml_len());
+        bytes.push(NAMEOP);
         path.to_aml_bytes(&mut bytes);
         inner.to_aml_bytes(&mut bytes);
         Name { bytes }
@@ -264,27 +279,30 @@ impl Name {
 /// Package object. 'children' represents the ACPI objects contained in this package.
 pub struct Package<'a> {
     children: Vec<&'a dyn Aml>,
+    len: usize,
 }
 
 impl Aml for Package<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = vec![self.children.len() as u8];
+        sink.byte(PACKAGEOP);
+        write_pkg_length(sink, self.len, true);
+        sink.byte(self.children.len() as u8);
         for child in &self.children {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
+    }
 
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
-        sink.byte(PACKAGEOP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.len)
     }
 }
 
 impl<'a> Package<'a> {
     /// Create Package object:
     pub fn new(children: Vec<&'a dyn Aml>) -> Self {
-        Package { children }
+        // NumElements followed by the elements
+        let len = 1 + children_len(&children);
+        Package { children, len }
     }
 }
 
@@ -296,13 +314,15 @@ pub struct PackageBuilder {
 
 impl Aml for PackageBuilder {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let pkg_length = create_pkg_length(self.data.len() + 1, true);
-
         sink.byte(PACKAGEOP);
-        sink.vec(&pkg_length);
+        write_pkg_length(sink, self.data.len() + 1, true);
         sink.byte(self.elements as u8);
         sink.vec(&self.data);
     }
+
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.data.len() + 1)
+    }
 }
 
 impl AmlSink for PackageBuilder {
@@ -325,6 +345,7 @@ impl PackageBuilder {
     }
 
     pub fn add_element(&mut self, aml: &dyn Aml) {
+        self.data.reserve(aml.aml_len());
         aml.to_aml_bytes(self);
         self.elements += 1;
     }
@@ -339,25 +360,28 @@ impl Default for PackageBuilder {
 /// Variable Package Term
 pub struct VarPackageTerm<'a> {
     data: &'a dyn Aml,
+    len: usize,
 }
 
 impl Aml for VarPackageTerm<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.data.to_aml_bytes(&mut bytes);
-
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
         sink.byte(VARPACKAGEOP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+        write_pkg_length(sink, self.len, true);
+        self.data.to_aml_bytes(sink);
+    }
+
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.len)
     }
 }
 
 impl<'a> VarPackageTerm<'a> {
     /// Create Variable Package Term
     pub fn new(data: &'a dyn Aml) -> Self {
-        VarPackageTerm { data }
+        VarPackageTerm {
+            data,
+            len: data.aml_len(),
+        }
     }
 }
 
@@ -376,12 +400,10 @@ package length is 2**28."
 
 */
 
-/* Also used for NamedField but in that case the length is not included in itself */
-fn create_pkg_length(len: usize, include_self: bool) -> Vec<u8> {
-    let mut result = Vec::with_capacity(4);
-
+/// Number of bytes needed to encode a PkgLength for `len` bytes of data.
+fn pkg_length_len(len: usize) -> usize {
     /* PkgLength is inclusive and includes the length bytes */
-    let length_length = if len < (2usize.pow(6) - 1) {
+    if len < (2usize.pow(6) - 1) {
         1
     } else if len < (2usize.pow(12) - 2) {
         2
@@ -389,30 +411,42 @@ fn create_pkg_length(len: usize, include_self: bool) -> Vec<u8> {
         3
     } else {
         4
-    };
+    }
+}
 
+/* Also used for NamedField but in that case the length is not included in itself */
+fn write_pkg_length(sink: &mut dyn AmlSink, len: usize, include_self: bool) {
+    let length_length = pkg_length_len(len);
     let length = len + if include_self { length_length } else { 0 };
 
     match length_length {
-        1 => result.push(length as u8),
+        1 => sink.byte(length as u8),
         2 => {
-            result.push((1u8 << 6) | (length & 0xf) as u8);
-            result.push((length >> 4) as u8)
+            sink.byte((1u8 << 6) | (length & 0xf) as u8);
+            sink.byte((length >> 4) as u8)
         }
         3 => {
-            result.push((2u8 << 6) | (length & 0xf) as u8);
-            result.push((length >> 4) as u8);
-            result.push((length >> 12) as u8);
+            sink.byte((2u8 << 6) | (length & 0xf) as u8);
+            sink.byte((length >> 4) as u8);
+            sink.byte((length >> 12) as u8);
         }
         _ => {
-            result.push((3u8 << 6) | (length & 0xf) as u8);
-            result.push((length >> 4) as u8);
-            result.push((length >> 12) as u8);
-            result.push((length >> 20) as u8);
+            sink.byte((3u8 << 6) | (length & 0xf) as u8);
+            sink.byte((length >> 4) as u8);
+            sink.byte((length >> 12) as u8);
+            sink.byte((length >> 20) as u8);
         }
     }
+}
 
-    result
+/// Total size of an object made of `op_len` opcode bytes followed by
+/// a PkgLength covering `len` bytes of data.
+fn pkg_len(op_len: usize, len: usize) -> usize {
+    op_len + pkg_length_len(len) + len
+}
+
+fn children_len(children: &[&dyn Aml]) -> usize {
+    children.iter().map(|child| child.aml_len()).sum()
 }
 
 /// EISAName object. 'value' means the encoded u32 EisaIdString.
@@ -489,40 +523,49 @@ impl Aml for AmlString {
 /// ResouceTemplate object. 'children' represents the ACPI objects in it.
 pub struct ResourceTemplate<'a> {
     children: Vec<&'a dyn Aml>,
+    buffer_len: usize,
+}
+
+impl ResourceTemplate<'_> {
+    // PkgLength covers the encoded buffer length and the buffer data
+    fn pkg_data_len(&self) -> usize {
+        self.buffer_len.aml_len() + self.buffer_len
+    }
 }
 
 impl Aml for ResourceTemplate<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
+        sink.byte(BUFFEROP);
+        write_pkg_length(sink, self.pkg_data_len(), true);
+
+        // Buffer length is an encoded integer including buffer data
+        // and EndTag and checksum byte
+        self.buffer_len.to_aml_bytes(sink);
 
         // Add buffer data
         for child in &self.children {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
 
         // Mark with end and mark checksum as as always valid
-        bytes.push(ENDTAG);
-        bytes.push(0); /* zero checksum byte */
-
-        // Buffer length is an encoded integer including buffer data
-        // and EndTag and checksum byte
-        let mut buffer_length = Vec::with_capacity(4);
-        bytes.len().to_aml_bytes(&mut buffer_length);
-
-        // PkgLength is everything else
-        let pkg_length = create_pkg_length(bytes.len() + buffer_length.len(), true);
+        sink.byte(ENDTAG);
+        sink.byte(0); /* zero checksum byte */
+    }
 
-        sink.byte(BUFFEROP);
-        sink.vec(&pkg_length);
-        sink.vec(&buffer_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.pkg_data_len())
     }
 }
 
 impl<'a> ResourceTemplate<'a> {
     /// Create ResouceTemplate object
     pub fn new(children: Vec<&'a dyn Aml>) -> Self {
-        ResourceTemplate { children }
+        // Resource descriptors plus the EndTag and its checksum byte
+        let buffer_len = children_len(&children) + 2;
+        ResourceTemplate {
+            children,
+            buffer_len,
+        }
     }
 }
 
@@ -838,7 +881,7 @@ impl Register {
 impl Aml for Register {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
         sink.byte(REGDESC); /* Register Descriptor */
-        sink.word(0x12); // length
+        sink.word(0x0c); // length
         self.reg.to_aml_bytes(sink);
     }
 }
@@ -847,29 +890,34 @@ impl Aml for Register {
 pub struct Device<'a> {
     path: Path,
     children: Vec<&'a dyn Aml>,
+    len: usize,
 }
 
 impl Aml for Device<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.path.to_aml_bytes(&mut bytes);
+        sink.byte(EXTOPPREFIX); /* ExtOpPrefix */
+        sink.byte(DEVICEOP); /* DeviceOp */
+        write_pkg_length(sink, self.len, true);
+        self.path.to_aml_bytes(sink);
         for child in &self.children {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
+    }
 
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
-        sink.byte(EXTOPPREFIX); /* ExtOpPrefix */
-        sink.byte(DEVICEOP); /* DeviceOp */
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(2, self.len)
     }
 }
 
 impl<'a> Device<'a> {
     /// Create Device object
     pub fn new(path: Path, children: Vec<&'a dyn Aml>) -> Self {
-        Device { path, children }
+        let len = path.aml_len() + children_len(&children);
+        Device {
+            path,
+            children,
+            len,
+        }
     }
 }
 
@@ -877,45 +925,44 @@ impl<'a> Device<'a> {
 pub struct Scope<'a> {
     path: Path,
     children: Vec<&'a dyn Aml>,
+    len: usize,
 }
 
 impl Aml for Scope<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.path.to_aml_bytes(&mut bytes);
+        sink.byte(SCOPEOP);
+        write_pkg_length(sink, self.len, true);
+        self.path.to_aml_bytes(sink);
         for child in &self.children {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
+    }
 
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
-        sink.byte(SCOPEOP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.len)
     }
 }
 
 impl<'a> Scope<'a> {
     /// Create Scope object
     pub fn new(path: Path, children: Vec<&'a dyn Aml>) -> Self {
-        Scope { path, children }
+        let len = path.aml_len() + children_len(&children);
+        Scope {
+            path,
+            children,
+            len,
+        }
     }
 
     /// Create raw bytes representing a Scope from its children in raw bytes
-    pub fn raw(path: Path, mut children: Vec<u8>) -> Vec<u8> {
-        let mut bytes = Vec::new();
+    pub fn raw(path: Path, children: Vec<u8>) -> Vec<u8> {
+        let len = path.aml_len() + children.len();
+
+        let mut bytes = Vec::with_capacity(pkg_len(1, len));
         bytes.push(SCOPEOP);
+        write_pkg_length(&mut bytes, len, true);
         path.to_aml_bytes(&mut bytes);
-        bytes.append(&mut children);
-
-        let n = bytes.len(); // n >= 1
-        let pkg_length = create_pkg_length(n - 1, true);
-        let m = pkg_length.len();
-
-        // move everything after the SCOPEOP over and copy in pkg_length
-        bytes.resize(n + m, 0xFF);
-        bytes.as_mut_slice().copy_within(1..n, m + 1);
-        bytes.as_mut_slice()[1..m + 1].copy_from_slice(pkg_length.as_slice());
+        bytes.extend_from_slice(&children);
 
         bytes
     }
@@ -927,35 +974,38 @@ pub struct Method<'a> {
     children: Vec<&'a dyn Aml>,
     args: u8,
     serialized: bool,
+    len: usize,
 }
 
 impl<'a> Method<'a> {
     /// Create Method object.
     pub fn new(path: Path, args: u8, serialized: bool, children: Vec<&'a dyn Aml>) -> Self {
+        // Name, MethodFlags and the method body
+        let len = path.aml_len() + 1 + children_len(&children);
         Method {
             path,
             children,
             args,
             serialized,
+            len,
         }
     }
 }
 
 impl Aml for Method<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.path.to_aml_bytes(&mut bytes);
+        sink.byte(METHODOP);
+        write_pkg_length(sink, self.len, true);
+        self.path.to_aml_bytes(sink);
         let flags: u8 = (self.args & 0x7) | ((self.serialized as u8) << 3);
-        bytes.push(flags);
+        sink.byte(flags);
         for child in &self.children {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
+    }
 
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
-        sink.byte(METHODOP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.len)
     }
 }
 
@@ -1000,6 +1050,7 @@ pub struct Field {
     access_type: FieldAccessType,
     lock_rule: FieldLockRule,
     update_rule: FieldUpdateRule,
+    len: usize,
 }
 
 impl Field {
@@ -1011,46 +1062,92 @@ impl Field {
         update_rule: FieldUpdateRule,
         fields: Vec<FieldEntry>,
     ) -> Self {
+        // Name, FieldFlags and the FieldList
+        let len = path.aml_len()
+            + 1
+            + fields
+                .iter()
+                .map(|field| match field {
+                    FieldEntry::Named(name, length) => name.len() + pkg_length_len(*length),
+                    FieldEntry::Reserved(length) => 1 + pkg_length_len(*length),
+                })
+                .sum::<usize>();
         Field {
             path,
             access_type,
             lock_rule,
             update_rule,
             fields,
+            len,
         }
     }
 }
 
 impl Aml for Field {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.path.to_aml_bytes(&mut bytes);
+        sink.byte(EXTOPPREFIX);
+        sink.byte(FIELDOP);
+        write_pkg_length(sink, self.len, true);
+        self.path.to_aml_bytes(sink);
 
         let flags: u8 = self.access_type as u8
             | ((self.lock_rule as u8) << 4)
             | ((self.update_rule as u8) << 5);
-        bytes.push(flags);
+        sink.byte(flags);
 
         for field in self.fields.iter() {
             match field {
                 FieldEntry::Named(name, length) => {
-                    bytes.extend_from_slice(name);
-                    bytes.append(&mut create_pkg_length(*length, false));
+                    sink.vec(name);
+                    write_pkg_length(sink, *length, false);
                 }
                 FieldEntry::Reserved(length) => {
-                    bytes.push(0x0);
-                    bytes.append(&mut create_pkg_length(*length, false));
+                    sink.byte(0x0);
+                    write_pkg_length(sink, *length, false);
                 }
             }
         }
+    }
+
+    fn aml_len(&self) -> usize {
+        pkg_len(2, self.len)
+    }
+}
 
-        let pkg_length = create_pkg_length(bytes.len(), true);
+// Field covering a 32-bit register at `offset` bytes in `region`,
+// which must be aligned for the dword accesses
+pub(crate) fn register_field(region: &str, name: [u8; 4], offset: u32) -> Field {
+    assert_eq!(offset % 4, 0);
+    let mut fields = Vec::new();
+    if offset > 0 {
+        fields.push(FieldEntry::Reserved(offset as usize * 8));
+    }
+    fields.push(FieldEntry::Named(name, 32));
+    Field::new(
+        region.into(),
+        FieldAccessType::DWord,
+        FieldLockRule::NoLock,
+        FieldUpdateRule::Preserve,
+        fields,
+    )
+}
 
-        sink.byte(EXTOPPREFIX);
-        sink.byte(FIELDOP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+// Field covering the bits of the 8-bit status register at `offset`
+// bytes in `region`. Other bits are written as zeroes, so that
+// acknowledging an event does not trigger the others.
+pub(crate) fn status_field(region: &str, offset: u32, bits: &[[u8; 4]]) -> Field {
+    let mut fields = Vec::new();
+    if offset > 0 {
+        fields.push(FieldEntry::Reserved(offset as usize * 8));
     }
+    fields.extend(bits.iter().map(|name| FieldEntry::Named(*name, 1)));
+    Field::new(
+        region.into(),
+        FieldAccessType::Byte,
+        FieldLockRule::NoLock,
+        FieldUpdateRule::WriteAsZeroes,
+        fields,
+    )
 }
 
 /// The space type for OperationRegion object
@@ -1066,6 +1163,7 @@ pub enum OpRegionSpace {
     IPMI,
     GeneralPurposeIO,
     GenericSerialBus,
+    PlatformCommChannel,
 }
 
 /// OperationRegion object with region name, region space type, its offset and length.
@@ -1103,58 +1201,61 @@ impl Aml for OpRegion<'_> {
 pub struct If<'a> {
     predicate: &'a dyn Aml,
     if_children: Vec<&'a dyn Aml>,
+    len: usize,
 }
 
 impl<'a> If<'a> {
     /// Create If object.
     pub fn new(predicate: &'a dyn Aml, if_children: Vec<&'a dyn Aml>) -> Self {
+        let len = predicate.aml_len() + children_len(&if_children);
         If {
             predicate,
             if_children,
+            len,
         }
     }
 }
 
 impl Aml for If<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.predicate.to_aml_bytes(&mut bytes);
+        sink.byte(IFOP);
+        write_pkg_length(sink, self.len, true);
+        self.predicate.to_aml_bytes(sink);
         for child in self.if_children.iter() {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
+    }
 
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
-        sink.byte(IFOP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.len)
     }
 }
 
 /// Else object
 pub struct Else<'a> {
     body: Vec<&'a dyn Aml>,
+    len: usize,
 }
 
 impl<'a> Else<'a> {
     /// Create Else object.
     pub fn new(body: Vec<&'a dyn Aml>) -> Self {
-        Else { body }
+        let len = children_len(&body);
+        Else { body, len }
     }
 }
 
 impl Aml for Else<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
+        sink.byte(ELSEOP);
+        write_pkg_length(sink, self.len, true);
         for child in self.body.iter() {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
+    }
 
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
-        sink.byte(ELSEOP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.len)
     }
 }
 
@@ -1333,31 +1434,33 @@ impl Aml for Notify<'_> {
 pub struct While<'a> {
     predicate: &'a dyn Aml,
     while_children: Vec<&'a dyn Aml>,
+    len: usize,
 }
 
 impl<'a> While<'a> {
     /// Create While object.
     pub fn new(predicate: &'a dyn Aml, while_children: Vec<&'a dyn Aml>) -> Self {
+        let len = predicate.aml_len() + children_len(&while_children);
         While {
             predicate,
             while_children,
+            len,
         }
     }
 }
 
 impl Aml for While<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.predicate.to_aml_bytes(&mut bytes);
+        sink.byte(WHILEOP);
+        write_pkg_length(sink, self.len, true);
+        self.predicate.to_aml_bytes(sink);
         for child in self.while_children.iter() {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
+    }
 
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
-        sink.byte(WHILEOP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.len)
     }
 }
 
@@ -1557,25 +1660,28 @@ impl Aml for MethodCall<'_> {
 /// Buffer object with the TermArg in it.
 pub struct BufferTerm<'a> {
     data: &'a dyn Aml,
+    len: usize,
 }
 
 impl<'a> BufferTerm<'a> {
     /// Create BufferTerm object.
     pub fn new(data: &'a dyn Aml) -> Self {
-        BufferTerm { data }
+        BufferTerm {
+            data,
+            len: data.aml_len(),
+        }
     }
 }
 
 impl Aml for BufferTerm<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.data.to_aml_bytes(&mut bytes);
-
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
         sink.byte(BUFFEROP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+        write_pkg_length(sink, self.len, true);
+        self.data.to_aml_bytes(sink);
+    }
+
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.len)
     }
 }
 
@@ -1591,17 +1697,23 @@ impl BufferData {
     }
 }
 
+impl BufferData {
+    // PkgLength covers the encoded buffer size and the buffer data
+    fn pkg_data_len(&self) -> usize {
+        self.data.len().aml_len() + self.data.len()
+    }
+}
+
 impl Aml for BufferData {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
-        self.data.len().to_aml_bytes(&mut bytes);
-        bytes.extend_from_slice(&self.data);
-
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
         sink.byte(BUFFEROP);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+        write_pkg_length(sink, self.pkg_data_len(), true);
+        self.data.len().to_aml_bytes(sink);
+        sink.vec(&self.data);
+    }
+
+    fn aml_len(&self) -> usize {
+        pkg_len(1, self.pkg_data_len())
     }
 }
 
@@ -1678,6 +1790,10 @@ impl Aml for Uuid {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
         self.name.to_aml_bytes(sink)
     }
+
+    fn aml_len(&self) -> usize {
+        self.name.aml_len()
+    }
 }
 
 /// Power Resource object. 'children' represents Power Resource method.
@@ -1686,44 +1802,45 @@ pub struct PowerResource<'a> {
     level: u8,
     order: u16,
     children: Vec<&'a dyn Aml>,
+    len: usize,
 }
 
 impl<'a> PowerResource<'a> {
     /// Create Power Resouce object
     pub fn new(name: Path, level: u8, order: u16, children: Vec<&'a dyn Aml>) -> Self {
+        // Name, SystemLevel, ResourceOrder and the children
+        let len = name.aml_len() + 1 + 2 + children_len(&children);
         PowerResource {
             name,
             level,
             order,
             children,
+            len,
         }
     }
 }
 
 impl Aml for PowerResource<'_> {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
-        let mut bytes = Vec::new();
+        sink.byte(POWERRESOURCEOP);
+        sink.byte(EXTOPPREFIX);
+        // PkgLength
+        write_pkg_length(sink, self.len, true);
 
         // Add name string
-        self.name.to_aml_bytes(&mut bytes);
+        self.name.to_aml_bytes(sink);
         // Add system level
-        bytes.push(self.level);
+        sink.byte(self.level);
         // Add Resource Order
-        let orders = self.order.to_le_bytes();
-        bytes.push(orders[0]);
-        bytes.push(orders[1]);
+        sink.word(self.order);
         // Add child data
         for child in &self.children {
-            child.to_aml_bytes(&mut bytes);
+            child.to_aml_bytes(sink);
         }
+    }
 
-        // PkgLength
-        let pkg_length = create_pkg_length(bytes.len(), true);
-
-        sink.byte(POWERRESOURCEOP);
-        sink.byte(EXTOPPREFIX);
-        sink.vec(&pkg_length);
-        sink.vec(&bytes);
+    fn aml_len(&self) -> usize {
+        pkg_len(2, self.len)
     }
 }
 
@@ -1731,6 +1848,7 @@ impl Aml for PowerResource<'_> {
 mod tests {
     use super::*;
     use alloc::borrow::ToOwned;
+    use alloc::vec;
 
     #[test]
     fn test_device() {
@@ -2023,6 +2141,13 @@ mod tests {
 
     #[test]
     fn test_pkg_length() {
+        fn create_pkg_length(len: usize, include_self: bool) -> Vec<u8> {
+            let mut bytes = Vec::new();
+            write_pkg_length(&mut bytes, len, include_self);
+            assert_eq!(bytes.len(), pkg_length_len(len));
+            bytes
+        }
+
         assert_eq!(create_pkg_length(62, true), vec![63]);
         assert_eq!(
             create_pkg_length(64, true),
@@ -2314,6 +2439,55 @@ mod tests {
         assert_eq!(aml, &op_region_data[..]);
     }
 
+    #[test]
+    fn test_register_fields() {
+        /*
+            Field (PRST, DWordAcc, NoLock, Preserve)
+            {
+                Offset (0x08),
+                COEV,   32
+            }
+        */
+        let data = [
+            0x5B, 0x81, 0x0E, 0x50, 0x52, 0x53, 0x54, 0x03, 0x00, 0x40, 0x04, 0x43, 0x4F, 0x45,
+            0x56, 0x20,
+        ];
+        let mut bytes = Vec::new();
+        register_field("PRST", *b"COEV", 8).to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, data);
+
+        bytes.clear();
+        register_field("PRST", *b"CSEL", 0).to_aml_bytes(&mut bytes);
+        assert_eq!(
+            bytes,
+            [0x5B, 0x81, 0x0B, 0x50, 0x52, 0x53, 0x54, 0x03, 0x43, 0x53, 0x45, 0x4C, 0x20]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unaligned_register_field() {
+        register_field("PRST", *b"COEV", 6);
+    }
+
+    #[test]
+    fn test_pcc_op_region() {
+        /*
+            OperationRegion (PCC0, PCC, One, 0x20)
+        */
+        let op_region_data = [0x5Bu8, 0x80, 0x50, 0x43, 0x43, 0x30, 0x0A, 0x01, 0x0A, 0x20];
+        let mut aml = Vec::new();
+
+        OpRegion::new(
+            "PCC0".into(),
+            OpRegionSpace::PlatformCommChannel,
+            &1_usize,
+            &0x20_usize,
+        )
+        .to_aml_bytes(&mut aml);
+        assert_eq!(aml, &op_region_data[..]);
+    }
+
     #[test]
     fn test_arg_if() {
         /*
@@ -2772,4 +2946,53 @@ mod tests {
             assert_eq!(expected, aml);
         }
     }
+
+    #[test]
+    fn test_aml_len() {
+        fn check(aml: &dyn Aml) {
+            let mut bytes = Vec::new();
+            aml.to_aml_bytes(&mut bytes);
+            assert_eq!(aml.aml_len(), bytes.len());
+        }
+
+        // Large enough to need multi-byte PkgLength encodings
+        let data: Vec<u8> = (0..5000).map(|i| i as u8).collect();
+        let buffer = BufferData::new(data);
+        let names: Vec<Name> = (0..600)
+            .map(|i| Name::new("_UID".into(), &(i as u32)))
+            .collect();
+        let children: Vec<&dyn Aml> = names.iter().map(|n| n as &dyn Aml).collect();
+
+        check(&Path::new("\\_SB_.PCI0.S000"));
+        check(&buffer);
+        check(&BufferTerm::new(&buffer));
+        check(&Package::new(children.clone()));
+        check(&VarPackageTerm::new(&buffer));
+        check(&Device::new("DEV0".into(), children.clone()));
+        check(&Scope::new("\\_SB_".into(), children.clone()));
+        check(&Method::new("MTHD".into(), 1, true, children.clone()));
+        check(&If::new(&Equal::new(&Arg(0), &ONE), children.clone()));
+        check(&Else::new(children.clone()));
+        check(&While::new(
+            &LessThan::new(&Local(0), &8u8),
+            children.clone(),
+        ));
+        check(&PowerResource::new("PRIC".into(), 0, 0, children));
+        check(&ResourceTemplate::new(vec![
+            &Memory32Fixed::new(true, 0xE8000000, 0x10000000),
+            &AddressSpace::new_bus_number(0x0u16, 0xffu16),
+            &IO::new(0x3f8, 0x3f8, 0, 0x8),
+        ]));
+        check(&Field::new(
+            "PRST".into(),
+            FieldAccessType::Byte,
+            FieldLockRule::NoLock,
+            FieldUpdateRule::WriteAsZeroes,
+            vec![
+                FieldEntry::Reserved(32),
+                FieldEntry::Named(*b"CPEN", 1),
+                FieldEntry::Named(*b"CINS", 4000),
+            ],
+        ));
+    }
 }
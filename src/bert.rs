@@ -79,6 +79,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         bert.to_aml_bytes(&mut bytes);
+        assert_eq!(bert.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -83,6 +83,10 @@ impl Aml for CEDT {
             st.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[repr(u8)]
@@ -138,12 +142,17 @@ impl Aml for CxlHostBridge {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
         sink.byte(CedtStructureType::Chbs as u8);
         sink.byte(0); // reserved
-        sink.byte(Self::len() as u8);
+        sink.word(Self::len() as u16);
         sink.dword(self.host_bridge_uid);
         sink.dword(self.cxl_version as u32);
+        sink.dword(0); // reserved
         sink.qword(self.port_base);
         sink.qword(self.cxl_version.len() as u64);
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -305,6 +314,10 @@ impl Aml for CxlFixedMemory {
             sink.vec(target);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 /// CXL XOR Interleave Math Structure
@@ -349,6 +362,10 @@ impl Aml for XorInterleaveMath {
             sink.qword(*xormap);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 /// RCEC Downstream Port Association Structure
@@ -397,7 +414,7 @@ impl PortAssociation {
     }
 
     fn len() -> usize {
-        16
+        17
     }
 
     fn bdf(&self) -> u16 {
@@ -415,6 +432,10 @@ impl Aml for PortAssociation {
         sink.byte(self.protocol as u8);
         sink.qword(self.base_addr);
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 #[cfg(test)]
@@ -431,6 +452,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         cedt.to_aml_bytes(&mut bytes);
+        assert_eq!(cedt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -447,6 +469,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         cedt.to_aml_bytes(&mut bytes);
+        assert_eq!(cedt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -479,6 +502,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         cedt.to_aml_bytes(&mut bytes);
+        assert_eq!(cedt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -499,6 +523,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         cedt.to_aml_bytes(&mut bytes);
+        assert_eq!(cedt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -522,6 +547,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         cedt.to_aml_bytes(&mut bytes);
+        assert_eq!(cedt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -0,0 +1,450 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{format, vec, vec::Vec};
+use zerocopy::IntoBytes;
+
+use crate::{
+    aml::{
+        register_field, status_field, Acquire, Add, Arg, BufferData, Device, EISAName, Equal, If,
+        LessThan, Local, Method, MethodCall, Mutex, Name, Notify, OpRegion, OpRegionSpace, Path,
+        Release, Return, Store, While, ONE, ZERO,
+    },
+    madt::{Gicc, ProcessorLocalApic, RINTC},
+    Aml, AmlSink,
+};
+
+/// Layout of the register block through which the VMM reports the
+/// state of the CPUs. The registers other than the selector refer to
+/// the CPU whose `_UID` was last written to the selector.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CpuHotplugRegisters {
+    /// Base address of the block in system memory
+    pub address: u64,
+    /// Size of the block
+    pub length: u32,
+    /// Offset of the 32-bit CPU selector
+    pub selector: u32,
+    /// Offset of the 8-bit status register. Bit 0 is set when the CPU
+    /// is enabled, bits 1 and 2 when an insertion or a removal is
+    /// pending (write 1 to acknowledge), and writing 1 to bit 3 ejects
+    /// the CPU.
+    pub status: u32,
+    /// Offset of the 32-bit register receiving the `_OST` source event
+    pub ost_event: u32,
+    /// Offset of the 32-bit register receiving the `_OST` status code
+    pub ost_status: u32,
+}
+
+impl CpuHotplugRegisters {
+    /// Default layout, with the selector, status, `_OST` event and
+    /// `_OST` status registers at offsets 0, 4, 8 and 12.
+    pub fn new(address: u64) -> Self {
+        CpuHotplugRegisters {
+            address,
+            length: 16,
+            selector: 0,
+            status: 4,
+            ost_event: 8,
+            ost_status: 12,
+        }
+    }
+}
+
+/// MADT structure describing the interrupt controller of a CPU, as
+/// returned by its `_MAT` object
+pub enum CpuInterruptController {
+    LocalApic(ProcessorLocalApic),
+    Gicc(Gicc),
+    Rintc(RINTC),
+}
+
+impl CpuInterruptController {
+    fn processor_uid(&self) -> u32 {
+        match self {
+            CpuInterruptController::LocalApic(apic) => apic.processor_uid(),
+            CpuInterruptController::Gicc(gicc) => gicc.processor_uid(),
+            CpuInterruptController::Rintc(rintc) => rintc.processor_uid(),
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        match self {
+            CpuInterruptController::LocalApic(apic) => apic.as_bytes().to_vec(),
+            CpuInterruptController::Gicc(gicc) => gicc.as_bytes().to_vec(),
+            CpuInterruptController::Rintc(rintc) => rintc.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<ProcessorLocalApic> for CpuInterruptController {
+    fn from(apic: ProcessorLocalApic) -> Self {
+        CpuInterruptController::LocalApic(apic)
+    }
+}
+
+impl From<Gicc> for CpuInterruptController {
+    fn from(gicc: Gicc) -> Self {
+        CpuInterruptController::Gicc(gicc)
+    }
+}
+
+impl From<RINTC> for CpuInterruptController {
+    fn from(rintc: RINTC) -> Self {
+        CpuInterruptController::Rintc(rintc)
+    }
+}
+
+fn cpu_name(uid: u32) -> Path {
+    Path::new(&format!("C{uid:03X}"))
+}
+
+// Processor device of a single CPU, forwarding to the methods of the
+// container
+struct CpuDevice<'a> {
+    uid: u32,
+    controller: &'a CpuInterruptController,
+}
+
+impl Aml for CpuDevice<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let uid = self.uid;
+        let sta = MethodCall::new("CSTA".into(), vec![&uid]);
+        let ej0 = MethodCall::new("CEJ0".into(), vec![&uid]);
+        let ost = MethodCall::new("COST".into(), vec![&uid, &Arg(0), &Arg(1), &Arg(2)]);
+        Device::new(
+            cpu_name(uid),
+            vec![
+                &Name::new("_HID".into(), &"ACPI0007"),
+                &Name::new("_UID".into(), &uid),
+                &Name::new("_MAT".into(), &BufferData::new(self.controller.bytes())),
+                &Method::new("_STA".into(), 0, false, vec![&Return::new(&sta)]),
+                &Method::new("_EJ0".into(), 1, false, vec![&ej0]),
+                &Method::new("_OST".into(), 3, false, vec![&ost]),
+            ],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+// Notify the CPU selected by Arg0 with the value in Arg1
+struct CpuNotify {
+    uid: u32,
+}
+
+impl Aml for CpuNotify {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let cpu = cpu_name(self.uid);
+        If::new(
+            &Equal::new(&Arg(0), &self.uid),
+            vec![&Notify::new(&cpu, &Arg(1))],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+// Methods of the container accessing the registers of the CPUs
+struct ContainerMethods {
+    cpu_count: u32,
+}
+
+impl Aml for ContainerMethods {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let lock = Path::new("CPLK");
+        let selector = Path::new("CSEL");
+        let enabled = Path::new("CPEN");
+        let inserting = Path::new("CINS");
+        let removing = Path::new("CRMV");
+        let eject = Path::new("CEJF");
+        let ost_event = Path::new("COEV");
+        let ost_status = Path::new("COSC");
+
+        let acquire = Acquire::new(lock.clone(), 0xffff);
+        let release = Release::new(lock);
+        let select = Store::new(&selector, &Arg(0));
+
+        // CSTA(uid): _STA of the CPU
+        Method::new(
+            "CSTA".into(),
+            1,
+            true,
+            vec![
+                &acquire,
+                &select,
+                &Store::new(&Local(0), &ZERO),
+                &If::new(
+                    &Equal::new(&enabled, &ONE),
+                    vec![&Store::new(&Local(0), &0xfu8)],
+                ),
+                &release,
+                &Return::new(&Local(0)),
+            ],
+        )
+        .to_aml_bytes(sink);
+
+        // CEJ0(uid): eject the CPU
+        Method::new(
+            "CEJ0".into(),
+            1,
+            true,
+            vec![&acquire, &select, &Store::new(&eject, &ONE), &release],
+        )
+        .to_aml_bytes(sink);
+
+        // COST(uid, event, status, info): _OST of the CPU
+        Method::new(
+            "COST".into(),
+            4,
+            true,
+            vec![
+                &acquire,
+                &select,
+                &Store::new(&ost_event, &Arg(1)),
+                &Store::new(&ost_status, &Arg(2)),
+                &release,
+            ],
+        )
+        .to_aml_bytes(sink);
+
+        // CTFY(uid, value): notify the CPU
+        let notifies: Vec<CpuNotify> = (0..self.cpu_count).map(|uid| CpuNotify { uid }).collect();
+        Method::new(
+            "CTFY".into(),
+            2,
+            false,
+            notifies.iter().map(|n| n as &dyn Aml).collect(),
+        )
+        .to_aml_bytes(sink);
+
+        // CSCN(): notify the OS of the pending insertions and removals
+        let notify_insert = MethodCall::new("CTFY".into(), vec![&Local(0), &ONE]);
+        let notify_remove = MethodCall::new("CTFY".into(), vec![&Local(0), &3u8]);
+        Method::new(
+            "CSCN".into(),
+            0,
+            true,
+            vec![
+                &acquire,
+                &Store::new(&Local(0), &ZERO),
+                &While::new(
+                    &LessThan::new(&Local(0), &self.cpu_count),
+                    vec![
+                        &Store::new(&selector, &Local(0)),
+                        &If::new(
+                            &Equal::new(&inserting, &ONE),
+                            vec![&notify_insert, &Store::new(&inserting, &ONE)],
+                        ),
+                        &If::new(
+                            &Equal::new(&removing, &ONE),
+                            vec![&notify_remove, &Store::new(&removing, &ONE)],
+                        ),
+                        &Add::new(&Local(0), &Local(0), &ONE),
+                    ],
+                ),
+                &release,
+            ],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Processor container device (ACPI0010) holding hotpluggable CPUs.
+/// The `CSCN` method of the container notifies the OS of the pending
+/// insertions and removals, and is meant to be called when the VMM
+/// signals a CPU hotplug event, e.g. through a GED.
+pub struct CpuHotplug {
+    path: Path,
+    registers: CpuHotplugRegisters,
+    cpus: Vec<CpuInterruptController>,
+}
+
+impl CpuHotplug {
+    pub fn new(path: Path, registers: CpuHotplugRegisters) -> Self {
+        CpuHotplug {
+            path,
+            registers,
+            cpus: Vec::new(),
+        }
+    }
+
+    /// Add a CPU. The n-th CPU added has `_UID` n, which is the value
+    /// written to the selector to access its registers. The processor
+    /// UID of its MADT structure must be n as well.
+    pub fn add_cpu(&mut self, controller: impl Into<CpuInterruptController>) {
+        let controller = controller.into();
+        assert!(self.cpus.len() < 0x1000);
+        assert_eq!(
+            controller.processor_uid(),
+            self.cpus.len() as u32,
+            "processor UID of the MADT structure must match the CPU _UID"
+        );
+        self.cpus.push(controller);
+    }
+}
+
+impl Aml for CpuHotplug {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let cpu_count = self.cpus.len() as u32;
+
+        let hid = Name::new("_HID".into(), &"ACPI0010");
+        let cid = Name::new("_CID".into(), &EISAName::new("PNP0A05"));
+        let mutex = Mutex::new("CPLK".into(), 0);
+        let region = OpRegion::new(
+            "PRST".into(),
+            OpRegionSpace::SystemMemory,
+            &self.registers.address,
+            &self.registers.length,
+        );
+        let selector_field = register_field("PRST", *b"CSEL", self.registers.selector);
+        let status_field = status_field(
+            "PRST",
+            self.registers.status,
+            &[*b"CPEN", *b"CINS", *b"CRMV", *b"CEJF"],
+        );
+        let ost_event_field = register_field("PRST", *b"COEV", self.registers.ost_event);
+        let ost_status_field = register_field("PRST", *b"COSC", self.registers.ost_status);
+
+        let methods = ContainerMethods { cpu_count };
+
+        let cpus: Vec<CpuDevice> = self
+            .cpus
+            .iter()
+            .enumerate()
+            .map(|(uid, controller)| CpuDevice {
+                uid: uid as u32,
+                controller,
+            })
+            .collect();
+
+        let mut children: Vec<&dyn Aml> = vec![
+            &hid,
+            &cid,
+            &mutex,
+            &region,
+            &selector_field,
+            &status_field,
+            &ost_event_field,
+            &ost_status_field,
+            &methods,
+        ];
+        children.extend(cpus.iter().map(|cpu| cpu as &dyn Aml));
+
+        Device::new(self.path.clone(), children).to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::madt::EnabledStatus;
+
+    #[test]
+    fn test_status_method() {
+        /*
+        Method (CSTA, 1, Serialized)
+        {
+            Acquire (CPLK, 0xFFFF)
+            CSEL = Arg0
+            Local0 = Zero
+            If ((CPEN == One))
+            {
+                Local0 = 0x0F
+            }
+
+            Release (CPLK)
+            Return (Local0)
+        }
+        */
+        let data = [
+            0x14, 0x2B, 0x43, 0x53, 0x54, 0x41, 0x09, 0x5B, 0x23, 0x43, 0x50, 0x4C, 0x4B, 0xFF,
+            0xFF, 0x70, 0x68, 0x43, 0x53, 0x45, 0x4C, 0x70, 0x00, 0x60, 0xA0, 0x0B, 0x93, 0x43,
+            0x50, 0x45, 0x4E, 0x01, 0x70, 0x0A, 0x0F, 0x60, 0x5B, 0x27, 0x43, 0x50, 0x4C, 0x4B,
+            0xA4, 0x60,
+        ];
+        let mut bytes = Vec::new();
+        ContainerMethods { cpu_count: 1 }.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes[..data.len()], data);
+    }
+
+    #[test]
+    fn test_cpu_device() {
+        let apic = ProcessorLocalApic::new(1, 1, EnabledStatus::Enabled);
+        let controller = CpuInterruptController::from(apic);
+        let cpu = CpuDevice {
+            uid: 1,
+            controller: &controller,
+        };
+        let mut bytes = Vec::new();
+        cpu.to_aml_bytes(&mut bytes);
+        assert_eq!(cpu.aml_len(), bytes.len());
+
+        let sta = MethodCall::new("CSTA".into(), vec![&1u32]);
+        let ej0 = MethodCall::new("CEJ0".into(), vec![&1u32]);
+        let ost = MethodCall::new("COST".into(), vec![&1u32, &Arg(0), &Arg(1), &Arg(2)]);
+        let mut expected = Vec::new();
+        Device::new(
+            "C001".into(),
+            vec![
+                &Name::new("_HID".into(), &"ACPI0007"),
+                &Name::new("_UID".into(), &1u32),
+                &Name::new(
+                    "_MAT".into(),
+                    &BufferData::new(vec![0, 8, 1, 1, 1, 0, 0, 0]),
+                ),
+                &Method::new("_STA".into(), 0, false, vec![&Return::new(&sta)]),
+                &Method::new("_EJ0".into(), 1, false, vec![&ej0]),
+                &Method::new("_OST".into(), 3, false, vec![&ost]),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_cpu_hotplug() {
+        let mut hotplug =
+            CpuHotplug::new("_SB_.CPUS".into(), CpuHotplugRegisters::new(0xfed0_c000));
+        for uid in 0..3u32 {
+            hotplug.add_cpu(
+                Gicc::new(EnabledStatus::Enabled)
+                    .acpi_processor_uid(uid)
+                    .cpu_interface_number(uid)
+                    .mpidr(uid.into()),
+            );
+        }
+
+        let mut bytes = Vec::new();
+        hotplug.to_aml_bytes(&mut bytes);
+        assert_eq!(hotplug.aml_len(), bytes.len());
+
+        // Device (CPUS)
+        assert_eq!(bytes[0..2], [0x5B, 0x82]);
+        assert_eq!(bytes[4..13], *b"\x2e_SB_CPUS");
+        for name in [
+            b"CSTA", b"CEJ0", b"COST", b"CTFY", b"CSCN", b"C000", b"C001", b"C002",
+        ] {
+            assert!(bytes.windows(4).any(|w| w == name));
+        }
+        assert!(!bytes.windows(4).any(|w| w == b"C003"));
+
+        // _MAT of the last CPU is its GICC structure
+        let gicc = Gicc::new(EnabledStatus::Enabled)
+            .acpi_processor_uid(2)
+            .cpu_interface_number(2)
+            .mpidr(2);
+        let mut mat = Vec::new();
+        Name::new("_MAT".into(), &BufferData::new(gicc.as_bytes().to_vec())).to_aml_bytes(&mut mat);
+        assert!(bytes.windows(mat.len()).any(|w| w == mat));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_uid() {
+        let mut hotplug =
+            CpuHotplug::new("_SB_.CPUS".into(), CpuHotplugRegisters::new(0xfed0_c000));
+        hotplug.add_cpu(ProcessorLocalApic::new(1, 1, EnabledStatus::Enabled));
+    }
+}
@@ -0,0 +1,750 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{
+    aml::{Name, Package, PackageBuilder, Register, ResourceTemplate},
+    gas::GAS,
+    pcct::PccRegister,
+    Aml, AmlSink,
+};
+
+// Register descriptor wrapped in a resource template, as expected by
+// the power and performance objects
+struct RegisterBuffer(GAS);
+
+impl Aml for RegisterBuffer {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        ResourceTemplate::new(vec![&Register::new(self.0)]).to_aml_bytes(sink);
+    }
+}
+
+/// Type of a processor power state in `_CST`
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum CStateType {
+    C1 = 1,
+    C2 = 2,
+    C3 = 3,
+}
+
+/// Processor power state, entered by reading `register`
+pub struct CState {
+    register: GAS,
+    type_: CStateType,
+    latency: u16,
+    power: u32,
+}
+
+impl CState {
+    /// `latency` is the worst-case latency to enter and exit the state
+    /// in microseconds, and `power` the average power consumption of
+    /// the processor in the state in milliwatts.
+    pub fn new(register: GAS, type_: CStateType, latency: u16, power: u32) -> Self {
+        CState {
+            register,
+            type_,
+            latency,
+            power,
+        }
+    }
+}
+
+impl Aml for CState {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        Package::new(vec![
+            &RegisterBuffer(self.register),
+            &(self.type_ as u8),
+            &self.latency,
+            &self.power,
+        ])
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Processor power states (`_CST`) object
+#[derive(Default)]
+pub struct Cst {
+    states: Vec<CState>,
+}
+
+impl Cst {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_state(&mut self, state: CState) {
+        self.states.push(state);
+    }
+}
+
+impl Aml for Cst {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let count = self.states.len();
+        let mut package = PackageBuilder::new();
+        package.add_element(&count);
+        for state in &self.states {
+            package.add_element(state);
+        }
+        Name::new("_CST".into(), &package).to_aml_bytes(sink);
+    }
+}
+
+/// How a low power idle state is entered
+pub enum LpiEntryMethod {
+    /// Register to read, or to write for states in the functional
+    /// fixed hardware space
+    Register(GAS),
+    /// Value added to the entry method of the parent state, for states
+    /// that can only be entered along with a parent state
+    Integer(u64),
+}
+
+impl Aml for LpiEntryMethod {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        match self {
+            LpiEntryMethod::Register(register) => RegisterBuffer(*register).to_aml_bytes(sink),
+            LpiEntryMethod::Integer(value) => value.to_aml_bytes(sink),
+        }
+    }
+}
+
+/// Low power idle state of a processor or of a processor container
+pub struct LpiState {
+    min_residency: u32,
+    wake_latency: u32,
+    enabled: bool,
+    arch_context_lost: u32,
+    residency_counter_frequency: u32,
+    enabled_parent_state: u32,
+    entry_method: LpiEntryMethod,
+    residency_counter: Option<GAS>,
+    usage_counter: Option<GAS>,
+    name: String,
+}
+
+impl LpiState {
+    /// `min_residency` is the time in microseconds below which entering
+    /// the state is not worth it, and `wake_latency` the worst-case
+    /// time to exit it.
+    pub fn new(
+        name: &str,
+        min_residency: u32,
+        wake_latency: u32,
+        entry_method: LpiEntryMethod,
+    ) -> Self {
+        LpiState {
+            min_residency,
+            wake_latency,
+            enabled: true,
+            arch_context_lost: 0,
+            residency_counter_frequency: 0,
+            enabled_parent_state: 0,
+            entry_method,
+            residency_counter: None,
+            usage_counter: None,
+            name: name.into(),
+        }
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+
+    /// Architecture-specific flags of the context lost in the state
+    pub fn arch_context_lost(mut self, flags: u32) -> Self {
+        self.arch_context_lost = flags;
+        self
+    }
+
+    /// Index, starting from 1, of the deepest state of the parent that
+    /// can be entered along with this state
+    pub fn enabled_parent_state(mut self, index: u32) -> Self {
+        self.enabled_parent_state = index;
+        self
+    }
+
+    /// Counter of the time spent in the state, incremented at
+    /// `frequency` Hz, or 0 if it counts in the units of the
+    /// architectural timer
+    pub fn residency_counter(mut self, register: GAS, frequency: u32) -> Self {
+        self.residency_counter = Some(register);
+        self.residency_counter_frequency = frequency;
+        self
+    }
+
+    /// Counter of the times the state was entered
+    pub fn usage_counter(mut self, register: GAS) -> Self {
+        self.usage_counter = Some(register);
+        self
+    }
+}
+
+impl Aml for LpiState {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        // Unsupported counters are null registers
+        let null = RegisterBuffer(GAS::default());
+        let residency_counter = self.residency_counter.map(RegisterBuffer);
+        let usage_counter = self.usage_counter.map(RegisterBuffer);
+        Package::new(vec![
+            &self.min_residency,
+            &self.wake_latency,
+            &(self.enabled as u32),
+            &self.arch_context_lost,
+            &self.residency_counter_frequency,
+            &self.enabled_parent_state,
+            &self.entry_method,
+            residency_counter.as_ref().unwrap_or(&null),
+            usage_counter.as_ref().unwrap_or(&null),
+            &self.name,
+        ])
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Low power idle states (`_LPI`) object of a processor or of a
+/// processor container
+pub struct Lpi {
+    level_id: u64,
+    states: Vec<LpiState>,
+}
+
+impl Lpi {
+    /// `level_id` identifies the level of the hierarchy the states
+    /// belong to, as used by the entry methods of the children states.
+    pub fn new(level_id: u64) -> Self {
+        Lpi {
+            level_id,
+            states: Vec::new(),
+        }
+    }
+
+    pub fn add_state(&mut self, state: LpiState) {
+        self.states.push(state);
+    }
+}
+
+impl Aml for Lpi {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let revision = 0u8;
+        let count = self.states.len();
+        let mut package = PackageBuilder::new();
+        package.add_element(&revision);
+        package.add_element(&self.level_id);
+        package.add_element(&count);
+        for state in &self.states {
+            package.add_element(state);
+        }
+        Name::new("_LPI".into(), &package).to_aml_bytes(sink);
+    }
+}
+
+/// Processor performance state
+pub struct PState {
+    frequency: u32,
+    power: u32,
+    latency: u32,
+    bus_master_latency: u32,
+    control: u32,
+    status: u32,
+}
+
+impl PState {
+    /// `frequency` is the core frequency in the state in MHz, `power`
+    /// its power consumption in milliwatts, and `latency` and
+    /// `bus_master_latency` the worst-case times in microseconds the
+    /// processor and the bus masters are unavailable during a
+    /// transition. `control` is written to the control register of
+    /// `_PCT` to enter the state, and `status` is the value read from
+    /// its status register once done.
+    pub fn new(
+        frequency: u32,
+        power: u32,
+        latency: u32,
+        bus_master_latency: u32,
+        control: u32,
+        status: u32,
+    ) -> Self {
+        PState {
+            frequency,
+            power,
+            latency,
+            bus_master_latency,
+            control,
+            status,
+        }
+    }
+}
+
+impl Aml for PState {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        Package::new(vec![
+            &self.frequency,
+            &self.power,
+            &self.latency,
+            &self.bus_master_latency,
+            &self.control,
+            &self.status,
+        ])
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Performance supported states (`_PSS`) object, listing the states
+/// from the highest performance to the lowest
+#[derive(Default)]
+pub struct Pss {
+    states: Vec<PState>,
+}
+
+impl Pss {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_state(&mut self, state: PState) {
+        self.states.push(state);
+    }
+}
+
+impl Aml for Pss {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let mut package = PackageBuilder::new();
+        for state in &self.states {
+            package.add_element(state);
+        }
+        Name::new("_PSS".into(), &package).to_aml_bytes(sink);
+    }
+}
+
+/// Performance control (`_PCT`) object, with the registers used to
+/// request a performance state and to read the current one
+pub struct Pct {
+    control: GAS,
+    status: GAS,
+}
+
+impl Pct {
+    pub fn new(control: GAS, status: GAS) -> Self {
+        Pct { control, status }
+    }
+}
+
+impl Aml for Pct {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        Name::new(
+            "_PCT".into(),
+            &Package::new(vec![
+                &RegisterBuffer(self.control),
+                &RegisterBuffer(self.status),
+            ]),
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Coordination of the state transitions of the processors of a
+/// dependency domain
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Coordination {
+    /// The OS requests the transition on all the processors
+    SwAll = 0xfc,
+    /// The OS requests the transition on any of the processors
+    SwAny = 0xfd,
+    /// The hardware coordinates the transitions requested on each
+    /// processor
+    HwAll = 0xfe,
+}
+
+/// P-State dependency (`_PSD`) object
+pub struct Psd {
+    domain: u32,
+    coordination: Coordination,
+    processors: u32,
+}
+
+impl Psd {
+    /// Make the processor part of `domain`, made of `processors`
+    /// processors.
+    pub fn new(domain: u32, coordination: Coordination, processors: u32) -> Self {
+        Psd {
+            domain,
+            coordination,
+            processors,
+        }
+    }
+}
+
+impl Aml for Psd {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let entries = 5u8;
+        let revision = 0u8;
+        let coordination = self.coordination as u32;
+        Name::new(
+            "_PSD".into(),
+            &Package::new(vec![&Package::new(vec![
+                &entries,
+                &revision,
+                &self.domain,
+                &coordination,
+                &self.processors,
+            ])]),
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Entry of the `_CPC` object, either an integer or a register
+pub enum CpcValue {
+    Integer(u32),
+    Register(GAS),
+    /// Register in the shared memory of a PCCT subspace
+    PccRegister(PccRegister),
+}
+
+impl From<u32> for CpcValue {
+    fn from(value: u32) -> Self {
+        CpcValue::Integer(value)
+    }
+}
+
+impl From<GAS> for CpcValue {
+    fn from(register: GAS) -> Self {
+        CpcValue::Register(register)
+    }
+}
+
+impl From<PccRegister> for CpcValue {
+    fn from(register: PccRegister) -> Self {
+        CpcValue::PccRegister(register)
+    }
+}
+
+impl Aml for CpcValue {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        match self {
+            CpcValue::Integer(value) => value.to_aml_bytes(sink),
+            CpcValue::Register(register) => RegisterBuffer(*register).to_aml_bytes(sink),
+            CpcValue::PccRegister(register) => {
+                ResourceTemplate::new(vec![register]).to_aml_bytes(sink)
+            }
+        }
+    }
+}
+
+/// Entries of the `_CPC` object, in their order in the package. Some
+/// of them may only be registers, see the ACPI specification.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(usize)]
+pub enum CpcEntry {
+    HighestPerformance,
+    NominalPerformance,
+    LowestNonlinearPerformance,
+    LowestPerformance,
+    GuaranteedPerformance,
+    DesiredPerformance,
+    MinimumPerformance,
+    MaximumPerformance,
+    PerformanceReductionTolerance,
+    TimeWindow,
+    CounterWraparoundTime,
+    ReferencePerformanceCounter,
+    DeliveredPerformanceCounter,
+    PerformanceLimited,
+    CppcEnable,
+    AutonomousSelectionEnable,
+    AutonomousActivityWindow,
+    EnergyPerformancePreference,
+    ReferencePerformance,
+    LowestFrequency,
+    NominalFrequency,
+}
+
+const CPC_ENTRIES: usize = CpcEntry::NominalFrequency as usize + 1;
+
+/// Continuous performance control (`_CPC`) object, revision 3. Entries
+/// that are not set are reported as unsupported, as a null register
+/// for the registers and 0 for the integers.
+pub struct Cpc {
+    entries: [Option<CpcValue>; CPC_ENTRIES],
+}
+
+impl Default for Cpc {
+    fn default() -> Self {
+        Cpc {
+            entries: [const { None }; CPC_ENTRIES],
+        }
+    }
+}
+
+impl Cpc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entry(mut self, entry: CpcEntry, value: impl Into<CpcValue>) -> Self {
+        self.entries[entry as usize] = Some(value.into());
+        self
+    }
+}
+
+// Entries that are registers whenever supported
+fn cpc_register_only(index: usize) -> bool {
+    !matches!(
+        index,
+        0..=3 /* Highest to lowest performance */
+            | 10 /* Counter wraparound time */
+            | 15 /* Autonomous selection enable */
+            | 18..=20 /* Reference performance and frequencies */
+    )
+}
+
+impl Aml for Cpc {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        // Number of entries, including this one and the revision
+        let entries = CPC_ENTRIES + 2;
+        let revision = 3u8;
+        let null = RegisterBuffer(GAS::default());
+        let mut package = PackageBuilder::new();
+        package.add_element(&entries);
+        package.add_element(&revision);
+        for (index, entry) in self.entries.iter().enumerate() {
+            match entry {
+                Some(value) => package.add_element(value),
+                None if cpc_register_only(index) => package.add_element(&null),
+                None => package.add_element(&0u8),
+            }
+        }
+        Name::new("_CPC".into(), &package).to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas::{AccessSize, AddressSpace};
+
+    fn ffh(address: u64) -> GAS {
+        GAS::new(
+            AddressSpace::FunctionalFixedHardware,
+            64,
+            0,
+            AccessSize::Undefined,
+            address,
+        )
+    }
+
+    #[test]
+    fn test_pct() {
+        /*
+        Name (_PCT, Package (0x02)  // _PCT: Performance Control
+        {
+            ResourceTemplate ()
+            {
+                Register (FFixedHW, 0x40, 0x00, 0x0000000000000199, ,)
+            },
+            ResourceTemplate ()
+            {
+                Register (FFixedHW, 0x40, 0x00, 0x0000000000000198, ,)
+            }
+        })
+        */
+        let expected = [
+            0x08, 0x5f, 0x50, 0x43, 0x54, 0x12, 0x2c, 0x02, 0x11, 0x14, 0x0a, 0x11, 0x82, 0x0c,
+            0x00, 0x7f, 0x40, 0x00, 0x00, 0x99, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x79,
+            0x00, 0x11, 0x14, 0x0a, 0x11, 0x82, 0x0c, 0x00, 0x7f, 0x40, 0x00, 0x00, 0x98, 0x01,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x79, 0x00,
+        ];
+        let pct = Pct::new(ffh(0x199), ffh(0x198));
+        let mut bytes = Vec::new();
+        pct.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(pct.aml_len(), bytes.len());
+    }
+
+    #[test]
+    fn test_cst() {
+        let c1 = GAS::new(
+            AddressSpace::FunctionalFixedHardware,
+            1,
+            2,
+            AccessSize::ByteAccess,
+            0,
+        );
+        let c2 = GAS::new(AddressSpace::SystemIo, 8, 0, AccessSize::ByteAccess, 0x414);
+        let mut cst = Cst::new();
+        cst.add_state(CState::new(c1, CStateType::C1, 1, 1000));
+        cst.add_state(CState::new(c2, CStateType::C2, 100, 500));
+
+        let mut bytes = Vec::new();
+        cst.to_aml_bytes(&mut bytes);
+        assert_eq!(cst.aml_len(), bytes.len());
+
+        let register_c1 = Register::new(c1);
+        let register_c2 = Register::new(c2);
+        let mut expected = Vec::new();
+        Name::new(
+            "_CST".into(),
+            &Package::new(vec![
+                &2u8,
+                &Package::new(vec![
+                    &ResourceTemplate::new(vec![&register_c1]),
+                    &1u8,
+                    &1u16,
+                    &1000u32,
+                ]),
+                &Package::new(vec![
+                    &ResourceTemplate::new(vec![&register_c2]),
+                    &2u8,
+                    &100u16,
+                    &500u32,
+                ]),
+            ]),
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_lpi() {
+        let mut lpi = Lpi::new(0);
+        lpi.add_state(LpiState::new(
+            "WFI",
+            1,
+            1,
+            LpiEntryMethod::Register(ffh(0xffff_ffff)),
+        ));
+        lpi.add_state(
+            LpiState::new(
+                "PowerDown",
+                2500,
+                1500,
+                LpiEntryMethod::Integer(0x1000_0000),
+            )
+            .arch_context_lost(1)
+            .enabled_parent_state(1)
+            .usage_counter(ffh(0x10)),
+        );
+
+        let mut bytes = Vec::new();
+        lpi.to_aml_bytes(&mut bytes);
+        assert_eq!(lpi.aml_len(), bytes.len());
+
+        let null = Register::new(GAS::default());
+        let wfi = Register::new(ffh(0xffff_ffff));
+        let usage = Register::new(ffh(0x10));
+        let mut expected = Vec::new();
+        Name::new(
+            "_LPI".into(),
+            &Package::new(vec![
+                &0u8,
+                &0u8,
+                &2u8,
+                &Package::new(vec![
+                    &1u32,
+                    &1u32,
+                    &1u32,
+                    &0u32,
+                    &0u32,
+                    &0u32,
+                    &ResourceTemplate::new(vec![&wfi]),
+                    &ResourceTemplate::new(vec![&null]),
+                    &ResourceTemplate::new(vec![&null]),
+                    &"WFI",
+                ]),
+                &Package::new(vec![
+                    &2500u32,
+                    &1500u32,
+                    &1u32,
+                    &1u32,
+                    &0u32,
+                    &1u32,
+                    &0x1000_0000u32,
+                    &ResourceTemplate::new(vec![&null]),
+                    &ResourceTemplate::new(vec![&usage]),
+                    &"PowerDown",
+                ]),
+            ]),
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_pss_psd() {
+        let mut pss = Pss::new();
+        pss.add_state(PState::new(3000, 35000, 10, 10, 0x1e00, 0x1e00));
+        pss.add_state(PState::new(800, 10000, 10, 10, 0x0800, 0x0800));
+        let psd = Psd::new(0, Coordination::HwAll, 4);
+
+        let mut bytes = Vec::new();
+        pss.to_aml_bytes(&mut bytes);
+        psd.to_aml_bytes(&mut bytes);
+        assert_eq!(pss.aml_len() + psd.aml_len(), bytes.len());
+
+        let mut expected = Vec::new();
+        Name::new(
+            "_PSS".into(),
+            &Package::new(vec![
+                &Package::new(vec![
+                    &3000u32, &35000u32, &10u32, &10u32, &0x1e00u32, &0x1e00u32,
+                ]),
+                &Package::new(vec![
+                    &800u32, &10000u32, &10u32, &10u32, &0x0800u32, &0x0800u32,
+                ]),
+            ]),
+        )
+        .to_aml_bytes(&mut expected);
+        Name::new(
+            "_PSD".into(),
+            &Package::new(vec![&Package::new(vec![
+                &5u8, &0u8, &0u32, &0xfeu32, &4u32,
+            ])]),
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_cpc() {
+        let desired = GAS::new(
+            AddressSpace::PlatformCommunicationsChannel,
+            32,
+            0,
+            AccessSize::DwordAccess,
+            0x14,
+        );
+        let cpc = Cpc::new()
+            .entry(CpcEntry::HighestPerformance, 300)
+            .entry(CpcEntry::NominalPerformance, 200)
+            .entry(CpcEntry::LowestNonlinearPerformance, 100)
+            .entry(CpcEntry::LowestPerformance, 50)
+            .entry(CpcEntry::DesiredPerformance, desired);
+
+        let mut bytes = Vec::new();
+        cpc.to_aml_bytes(&mut bytes);
+        assert_eq!(cpc.aml_len(), bytes.len());
+
+        let null = Register::new(GAS::default());
+        let null = ResourceTemplate::new(vec![&null]);
+        let desired = Register::new(desired);
+        let desired = ResourceTemplate::new(vec![&desired]);
+        let mut expected = Vec::new();
+        Name::new(
+            "_CPC".into(),
+            &Package::new(vec![
+                &23u8, &3u8, &300u32, &200u32, &100u32, &50u32, &null, &desired, &null, &null,
+                &null, &null, &0u8, &null, &null, &null, &null, &0u8, &null, &null, &0u8, &0u8,
+                &0u8,
+            ]),
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+}
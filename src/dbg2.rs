@@ -0,0 +1,288 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::IntoBytes;
+
+extern crate alloc;
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{
+    gas::GAS,
+    spcr::{SerialPortSubType, SerialPortType},
+    u8sum, Aml, AmlSink, Checksum, TableHeader,
+};
+
+pub struct DBG2 {
+    header: TableHeader,
+    checksum: Checksum,
+    devices: Vec<Box<dyn Aml>>,
+}
+
+impl DBG2 {
+    const DEVICE_OFFSET: u32 = 44;
+
+    pub fn new(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
+        let mut header = TableHeader {
+            signature: *b"DBG2",
+            length: Self::DEVICE_OFFSET.into(),
+            revision: 0,
+            checksum: 0,
+            oem_id,
+            oem_table_id,
+            oem_revision: oem_revision.into(),
+            creator_id: crate::CREATOR_ID,
+            creator_revision: crate::CREATOR_REVISION,
+        };
+
+        let mut cksum = Checksum::default();
+        cksum.append(header.as_bytes());
+        cksum.append(Self::DEVICE_OFFSET.as_bytes());
+        header.checksum = cksum.value();
+
+        Self {
+            header,
+            checksum: cksum,
+            devices: Vec::new(),
+        }
+    }
+
+    fn update_header(&mut self, sum: u8, len: u32) {
+        let old_len = self.header.length.get();
+        let new_len = len + old_len;
+        self.header.length.set(new_len);
+
+        // Remove the bytes from the old length, add the new length
+        // and the new data.
+        self.checksum.delete(old_len.as_bytes());
+        self.checksum.append(new_len.as_bytes());
+        self.checksum.add(sum);
+
+        // The header also contains a count of the number of devices,
+        // so the sum needs an additional '1' added to it.
+        self.checksum.add(1);
+
+        self.header.checksum = self.checksum.value();
+    }
+
+    pub fn add_device(&mut self, device: DebugDevice) {
+        self.update_header(u8sum(&device), device.len() as u32);
+        self.devices.push(Box::new(device));
+    }
+}
+
+/// The Debug Port Table 2 describes the debug devices of the
+/// platform, such as the UARTs used by the kernel debuggers and early
+/// consoles.
+impl Aml for DBG2 {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.vec(self.header.as_bytes());
+        sink.dword(Self::DEVICE_OFFSET);
+        sink.dword(self.devices.len() as u32);
+
+        for device in &self.devices {
+            device.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
+}
+
+/// Type and subtype of a debug device
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DebugPort {
+    Serial(SerialPortSubType),
+    Ieee1394,
+    UsbXhci,
+    UsbEhci,
+    /// Network controller, identified by its PCI vendor ID
+    Net(u16),
+}
+
+impl DebugPort {
+    fn port_type(&self) -> SerialPortType {
+        match self {
+            DebugPort::Serial(_) => SerialPortType::Serial,
+            DebugPort::Ieee1394 => SerialPortType::Uart1394,
+            DebugPort::UsbXhci | DebugPort::UsbEhci => SerialPortType::Usb,
+            DebugPort::Net(_) => SerialPortType::Net,
+        }
+    }
+
+    fn port_subtype(&self) -> u16 {
+        match self {
+            DebugPort::Serial(subtype) => *subtype as u16,
+            DebugPort::Ieee1394 | DebugPort::UsbXhci => 0,
+            DebugPort::UsbEhci => 1,
+            DebugPort::Net(vendor_id) => *vendor_id,
+        }
+    }
+}
+
+/// Debug device information structure, made of the registers of the
+/// device along with the size of their ranges, its path in the
+/// namespace and optional OEM data
+pub struct DebugDevice {
+    port: DebugPort,
+    registers: Vec<(GAS, u32)>,
+    namespace: String,
+    oem_data: Vec<u8>,
+}
+
+impl DebugDevice {
+    const REGISTER_OFFSET: usize = 22;
+
+    /// `namespace` is the full path of the device in the namespace, or
+    /// `.` when the device is not described there
+    pub fn new(port: DebugPort, namespace: String) -> Self {
+        assert!(!namespace.is_empty());
+        Self {
+            port,
+            registers: Vec::new(),
+            namespace,
+            oem_data: Vec::new(),
+        }
+    }
+
+    /// Register block of the device at `register`, `size` bytes long
+    pub fn add_register(&mut self, register: GAS, size: u32) {
+        self.registers.push((register, size));
+    }
+
+    pub fn oem_data(mut self, oem_data: Vec<u8>) -> Self {
+        self.oem_data = oem_data;
+        self
+    }
+
+    fn address_size_offset(&self) -> usize {
+        Self::REGISTER_OFFSET + self.registers.len() * GAS::len()
+    }
+
+    fn namespace_offset(&self) -> usize {
+        self.address_size_offset() + self.registers.len() * 4
+    }
+
+    fn namespace_len(&self) -> usize {
+        // Null terminated
+        self.namespace.len() + 1
+    }
+
+    fn oem_data_offset(&self) -> usize {
+        if self.oem_data.is_empty() {
+            0
+        } else {
+            self.namespace_offset() + self.namespace_len()
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.namespace_offset() + self.namespace_len() + self.oem_data.len()
+    }
+}
+
+impl Aml for DebugDevice {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.byte(0); // revision
+        sink.word(self.len() as u16);
+        sink.byte(self.registers.len() as u8);
+        sink.word(self.namespace_len() as u16);
+        sink.word(self.namespace_offset() as u16);
+        sink.word(self.oem_data.len() as u16);
+        sink.word(self.oem_data_offset() as u16);
+        sink.word(self.port.port_type() as u16);
+        sink.word(self.port.port_subtype());
+        sink.word(0); // reserved
+        sink.word(Self::REGISTER_OFFSET as u16);
+        sink.word(self.address_size_offset() as u16);
+
+        for (register, _) in &self.registers {
+            register.to_aml_bytes(sink);
+        }
+        for (_, size) in &self.registers {
+            sink.dword(*size);
+        }
+        sink.vec(self.namespace.as_bytes());
+        sink.byte(0);
+        sink.vec(&self.oem_data);
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas::{AccessSize, AddressSpace};
+    use crate::test_utils::check_table;
+    use alloc::vec;
+
+    #[test]
+    fn test_dbg2() {
+        let dbg2 = DBG2::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
+        let bytes = check_table(&dbg2);
+        assert_eq!(bytes.len(), 44);
+        assert_eq!(bytes[0..4], *b"DBG2");
+        assert_eq!(bytes[36..44], [44, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_pl011() {
+        let mut dbg2 = DBG2::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
+        let mut device = DebugDevice::new(
+            DebugPort::Serial(SerialPortSubType::ArmPl011),
+            "\\_SB.COM0".into(),
+        );
+        device.add_register(
+            GAS::new(
+                AddressSpace::SystemMemory,
+                32,
+                0,
+                AccessSize::DwordAccess,
+                0x900_0000,
+            ),
+            0x1000,
+        );
+        dbg2.add_device(device);
+
+        let bytes = check_table(&dbg2);
+        assert_eq!(bytes[40..44], [1, 0, 0, 0]);
+        assert_eq!(
+            bytes[44..],
+            vec![
+                0, 48, 0, 1, // revision, length, registers
+                10, 0, 38, 0, // namespace
+                0, 0, 0, 0, // OEM data
+                0x00, 0x80, 3, 0, 0, 0, // serial, PL011
+                22, 0, 34, 0, // register and address size offsets
+                0, 32, 0, 3, 0x00, 0x00, 0x00, 0x09, 0, 0, 0, 0, // register
+                0x00, 0x10, 0, 0, // size
+                b'\\', b'_', b'S', b'B', b'.', b'C', b'O', b'M', b'0', 0,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_oem_data() {
+        let mut dbg2 = DBG2::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
+        dbg2.add_device(DebugDevice::new(DebugPort::Net(0x8086), ".".into()));
+        dbg2.add_device(
+            DebugDevice::new(DebugPort::UsbEhci, ".".into()).oem_data(vec![0xde, 0xad]),
+        );
+
+        let bytes = check_table(&dbg2);
+        assert_eq!(bytes[40..44], [2, 0, 0, 0]);
+        assert_eq!(bytes.len(), 44 + 24 + 26);
+        // Net device with the PCI vendor ID as subtype
+        assert_eq!(bytes[44 + 12..44 + 16], [0x03, 0x80, 0x86, 0x80]);
+        // OEM data after the namespace string
+        let usb = 44 + 24;
+        assert_eq!(bytes[usb + 8..usb + 12], [2, 0, 24, 0]);
+        assert_eq!(bytes[usb + 12..usb + 16], [0x02, 0x80, 1, 0]);
+        assert_eq!(bytes[usb + 22..], [b'.', 0, 0xde, 0xad]);
+    }
+}
@@ -0,0 +1,581 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::IntoBytes;
+
+extern crate alloc;
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{u8sum, Aml, AmlSink, Checksum, TableHeader};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DmarFlags {
+    InterruptRemapping = 1 << 0,
+    X2ApicOptOut = 1 << 1,
+    DmaCtrlPlatformOptIn = 1 << 2,
+}
+
+pub struct DMAR {
+    header: TableHeader,
+    checksum: Checksum,
+    flags: u8,
+    host_address_width: u8,
+    last_type: StructureType,
+    structures: Vec<Box<dyn Aml>>,
+}
+
+impl DMAR {
+    /// `host_address_width` is the maximum DMA physical addressability
+    /// of the platform, in bits
+    pub fn new(
+        oem_id: [u8; 6],
+        oem_table_id: [u8; 8],
+        oem_revision: u32,
+        host_address_width: u8,
+    ) -> Self {
+        assert!(host_address_width > 0 && host_address_width <= 64);
+
+        let mut header = TableHeader {
+            signature: *b"DMAR",
+            length: (Self::header_len() as u32).into(),
+            revision: 1,
+            checksum: 0,
+            oem_id,
+            oem_table_id,
+            oem_revision: oem_revision.into(),
+            creator_id: crate::CREATOR_ID,
+            creator_revision: crate::CREATOR_REVISION,
+        };
+
+        // The width is encoded minus one
+        let host_address_width = host_address_width - 1;
+        let mut cksum = Checksum::default();
+        cksum.append(header.as_bytes());
+        cksum.add(host_address_width);
+        header.checksum = cksum.value();
+
+        Self {
+            header,
+            checksum: cksum,
+            flags: 0,
+            host_address_width,
+            last_type: StructureType::Drhd,
+            structures: Vec::new(),
+        }
+    }
+
+    fn header_len() -> usize {
+        TableHeader::len() + 12
+    }
+
+    pub fn flag(mut self, flag: DmarFlags) -> Self {
+        let flags = self.flags | flag as u8;
+        self.checksum.sub(self.flags);
+        self.checksum.add(flags);
+        self.flags = flags;
+        self.header.checksum = self.checksum.value();
+        self
+    }
+
+    fn update_header(&mut self, sum: u8, len: u32) {
+        let old_len = self.header.length.get();
+        let new_len = len + old_len;
+        self.header.length.set(new_len);
+
+        // Remove the bytes from the old length, add the new length
+        // and the new data.
+        self.checksum.delete(old_len.as_bytes());
+        self.checksum.append(new_len.as_bytes());
+        self.checksum.add(sum);
+
+        self.header.checksum = self.checksum.value();
+    }
+
+    // The remapping structures must be listed by type, in the order of
+    // their type values
+    fn add_structure(&mut self, structure_type: StructureType, structure: Box<dyn Aml>) {
+        assert!(structure_type as u16 >= self.last_type as u16);
+        self.last_type = structure_type;
+        self.update_header(u8sum(structure.as_ref()), structure.aml_len() as u32);
+        self.structures.push(structure);
+    }
+
+    pub fn add_drhd(&mut self, drhd: Drhd) {
+        self.add_structure(StructureType::Drhd, Box::new(drhd));
+    }
+
+    pub fn add_rmrr(&mut self, rmrr: Rmrr) {
+        self.add_structure(StructureType::Rmrr, Box::new(rmrr));
+    }
+
+    pub fn add_atsr(&mut self, atsr: Atsr) {
+        self.add_structure(StructureType::Atsr, Box::new(atsr));
+    }
+
+    pub fn add_rhsa(&mut self, rhsa: Rhsa) {
+        self.add_structure(StructureType::Rhsa, Box::new(rhsa));
+    }
+
+    pub fn add_satc(&mut self, satc: Satc) {
+        self.add_structure(StructureType::Satc, Box::new(satc));
+    }
+}
+
+/// The DMA Remapping table describes the Intel VT-d remapping
+/// hardware units of the platform and the devices under their scope.
+impl Aml for DMAR {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.vec(self.header.as_bytes());
+        sink.byte(self.host_address_width);
+        sink.byte(self.flags);
+        sink.vec(&[0; 10]); // reserved
+
+        for st in &self.structures {
+            st.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u16)]
+enum StructureType {
+    Drhd = 0,
+    Rmrr = 1,
+    Atsr = 2,
+    Rhsa = 3,
+    Satc = 5,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum DeviceScopeType {
+    PciEndpoint = 1,
+    PciSubHierarchy = 2,
+    IoApic = 3,
+    Hpet = 4,
+}
+
+/// Device under the scope of a remapping structure, identified by the
+/// path from its start bus through the bridges down to the device.
+/// Each element of the path is a (device, function) pair.
+pub struct DeviceScope {
+    scope_type: DeviceScopeType,
+    enumeration_id: u8,
+    start_bus: u8,
+    path: Vec<(u8, u8)>,
+}
+
+impl DeviceScope {
+    fn new(
+        scope_type: DeviceScopeType,
+        enumeration_id: u8,
+        start_bus: u8,
+        path: &[(u8, u8)],
+    ) -> Self {
+        assert!(!path.is_empty());
+        for (device, function) in path {
+            assert!(*device < 32);
+            assert!(*function < 8);
+        }
+
+        Self {
+            scope_type,
+            enumeration_id,
+            start_bus,
+            path: path.into(),
+        }
+    }
+
+    pub fn pci_endpoint(start_bus: u8, path: &[(u8, u8)]) -> Self {
+        Self::new(DeviceScopeType::PciEndpoint, 0, start_bus, path)
+    }
+
+    /// PCI-PCI bridge, along with all the devices behind it
+    pub fn pci_bridge(start_bus: u8, path: &[(u8, u8)]) -> Self {
+        Self::new(DeviceScopeType::PciSubHierarchy, 0, start_bus, path)
+    }
+
+    /// I/O APIC whose ID is `ioapic_id`, as in the MADT
+    pub fn ioapic(ioapic_id: u8, start_bus: u8, path: &[(u8, u8)]) -> Self {
+        Self::new(DeviceScopeType::IoApic, ioapic_id, start_bus, path)
+    }
+
+    /// MSI capable HPET whose number is `hpet_number`, as in the HPET
+    /// table
+    pub fn hpet(hpet_number: u8, start_bus: u8, path: &[(u8, u8)]) -> Self {
+        Self::new(DeviceScopeType::Hpet, hpet_number, start_bus, path)
+    }
+
+    fn len(&self) -> usize {
+        6 + 2 * self.path.len()
+    }
+}
+
+impl Aml for DeviceScope {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.byte(self.scope_type as u8);
+        sink.byte(self.len() as u8);
+        sink.byte(0); // flags
+        sink.byte(0); // reserved
+        sink.byte(self.enumeration_id);
+        sink.byte(self.start_bus);
+        for (device, function) in &self.path {
+            sink.byte(*device);
+            sink.byte(*function);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+fn scopes_len(scopes: &[DeviceScope]) -> usize {
+    scopes.iter().map(|scope| scope.len()).sum()
+}
+
+/// DMA Remapping Hardware Unit Definition
+pub struct Drhd {
+    segment: u16,
+    base_address: u64,
+    include_pci_all: bool,
+    size: u8,
+    device_scopes: Vec<DeviceScope>,
+}
+
+impl Drhd {
+    pub fn new(segment: u16, base_address: u64) -> Self {
+        Self {
+            segment,
+            base_address,
+            include_pci_all: false,
+            size: 0,
+            device_scopes: Vec::new(),
+        }
+    }
+
+    /// The unit covers all the PCI devices of the segment that are not
+    /// under the scope of another unit. It must then be the last unit
+    /// of the segment, and only I/O APIC and HPET scopes are expected.
+    pub fn include_pci_all(mut self) -> Self {
+        self.include_pci_all = true;
+        self
+    }
+
+    /// Size of the register set, as a power of two of 4KiB pages
+    pub fn register_size(mut self, pages_log2: u8) -> Self {
+        assert!(pages_log2 < 16);
+        self.size = pages_log2;
+        self
+    }
+
+    pub fn add_device_scope(&mut self, scope: DeviceScope) {
+        self.device_scopes.push(scope);
+    }
+
+    fn len(&self) -> usize {
+        16 + scopes_len(&self.device_scopes)
+    }
+}
+
+impl Aml for Drhd {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(StructureType::Drhd as u16);
+        sink.word(self.len() as u16);
+        sink.byte(self.include_pci_all as u8);
+        sink.byte(self.size);
+        sink.word(self.segment);
+        sink.qword(self.base_address);
+        for scope in &self.device_scopes {
+            scope.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Reserved Memory Region, which must stay identity mapped for the
+/// devices under its scope
+pub struct Rmrr {
+    segment: u16,
+    base_address: u64,
+    limit_address: u64,
+    device_scopes: Vec<DeviceScope>,
+}
+
+impl Rmrr {
+    /// The region spans from `base_address` to `limit_address`, both
+    /// included
+    pub fn new(segment: u16, base_address: u64, limit_address: u64) -> Self {
+        assert!(base_address & 0xfff == 0);
+        assert!(limit_address & 0xfff == 0xfff);
+        assert!(base_address < limit_address);
+
+        Self {
+            segment,
+            base_address,
+            limit_address,
+            device_scopes: Vec::new(),
+        }
+    }
+
+    pub fn add_device_scope(&mut self, scope: DeviceScope) {
+        self.device_scopes.push(scope);
+    }
+
+    fn len(&self) -> usize {
+        24 + scopes_len(&self.device_scopes)
+    }
+}
+
+impl Aml for Rmrr {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(StructureType::Rmrr as u16);
+        sink.word(self.len() as u16);
+        sink.word(0); // reserved
+        sink.word(self.segment);
+        sink.qword(self.base_address);
+        sink.qword(self.limit_address);
+        for scope in &self.device_scopes {
+            scope.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Root Port ATS Capability, listing the root ports supporting Address
+/// Translation Services
+pub struct Atsr {
+    segment: u16,
+    all_ports: bool,
+    device_scopes: Vec<DeviceScope>,
+}
+
+impl Atsr {
+    pub fn new(segment: u16) -> Self {
+        Self {
+            segment,
+            all_ports: false,
+            device_scopes: Vec::new(),
+        }
+    }
+
+    /// All the root ports of the segment support ATS
+    pub fn all_ports(mut self) -> Self {
+        self.all_ports = true;
+        self
+    }
+
+    pub fn add_device_scope(&mut self, scope: DeviceScope) {
+        assert!(scope.scope_type == DeviceScopeType::PciSubHierarchy);
+        self.device_scopes.push(scope);
+    }
+
+    fn len(&self) -> usize {
+        8 + scopes_len(&self.device_scopes)
+    }
+}
+
+impl Aml for Atsr {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(StructureType::Atsr as u16);
+        sink.word(self.len() as u16);
+        sink.byte(self.all_ports as u8);
+        sink.byte(0); // reserved
+        sink.word(self.segment);
+        for scope in &self.device_scopes {
+            scope.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Remapping Hardware Static Affinity, giving the proximity domain of
+/// the unit whose registers are at `base_address`
+pub struct Rhsa {
+    base_address: u64,
+    proximity_domain: u32,
+}
+
+impl Rhsa {
+    pub fn new(base_address: u64, proximity_domain: u32) -> Self {
+        Self {
+            base_address,
+            proximity_domain,
+        }
+    }
+
+    fn len() -> usize {
+        20
+    }
+}
+
+impl Aml for Rhsa {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(StructureType::Rhsa as u16);
+        sink.word(Self::len() as u16);
+        sink.dword(0); // reserved
+        sink.qword(self.base_address);
+        sink.dword(self.proximity_domain);
+    }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
+}
+
+/// SoC Integrated Address Translation Cache, listing the SoC
+/// integrated devices with an ATC
+pub struct Satc {
+    segment: u16,
+    atc_required: bool,
+    device_scopes: Vec<DeviceScope>,
+}
+
+impl Satc {
+    pub fn new(segment: u16) -> Self {
+        Self {
+            segment,
+            atc_required: false,
+            device_scopes: Vec::new(),
+        }
+    }
+
+    /// The devices are only functional with their ATC enabled
+    pub fn atc_required(mut self) -> Self {
+        self.atc_required = true;
+        self
+    }
+
+    pub fn add_device_scope(&mut self, scope: DeviceScope) {
+        assert!(scope.scope_type == DeviceScopeType::PciEndpoint);
+        self.device_scopes.push(scope);
+    }
+
+    fn len(&self) -> usize {
+        8 + scopes_len(&self.device_scopes)
+    }
+}
+
+impl Aml for Satc {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(StructureType::Satc as u16);
+        sink.word(self.len() as u16);
+        sink.byte(self.atc_required as u8);
+        sink.byte(0); // reserved
+        sink.word(self.segment);
+        for scope in &self.device_scopes {
+            scope.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_table;
+    use alloc::vec;
+
+    #[test]
+    fn test_dmar() {
+        let dmar = DMAR::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef, 39)
+            .flag(DmarFlags::InterruptRemapping)
+            .flag(DmarFlags::X2ApicOptOut);
+        let bytes = check_table(&dmar);
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(bytes[0..4], *b"DMAR");
+        assert_eq!(bytes[36..48], [38, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_drhd() {
+        let mut drhd = Drhd::new(0, 0xfed9_0000).include_pci_all();
+        drhd.add_device_scope(DeviceScope::ioapic(0, 0xf0, &[(0x1f, 0)]));
+        drhd.add_device_scope(DeviceScope::hpet(0, 0, &[(0x1f, 0)]));
+
+        let mut bytes = Vec::new();
+        drhd.to_aml_bytes(&mut bytes);
+        assert_eq!(drhd.aml_len(), bytes.len());
+        assert_eq!(
+            bytes,
+            vec![
+                0, 0, 32, 0, 1, 0, 0, 0, 0x00, 0x00, 0xd9, 0xfe, 0, 0, 0, 0, // DRHD
+                3, 8, 0, 0, 0, 0xf0, 0x1f, 0, // I/O APIC
+                4, 8, 0, 0, 0, 0, 0x1f, 0, // HPET
+            ]
+        );
+    }
+
+    #[test]
+    fn test_structures() {
+        let mut dmar = DMAR::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef, 48)
+            .flag(DmarFlags::InterruptRemapping);
+
+        let mut drhd = Drhd::new(0, 0xfed9_1000);
+        drhd.add_device_scope(DeviceScope::pci_endpoint(0, &[(2, 0)]));
+        dmar.add_drhd(drhd);
+        dmar.add_drhd(Drhd::new(0, 0xfed9_0000).include_pci_all());
+        check_table(&dmar);
+
+        let mut rmrr = Rmrr::new(0, 0x7c00_0000, 0x7fff_ffff);
+        rmrr.add_device_scope(DeviceScope::pci_endpoint(0, &[(0x14, 0)]));
+        dmar.add_rmrr(rmrr);
+
+        let mut atsr = Atsr::new(0);
+        atsr.add_device_scope(DeviceScope::pci_bridge(0, &[(1, 0)]));
+        dmar.add_atsr(atsr);
+        dmar.add_rhsa(Rhsa::new(0xfed9_0000, 1));
+
+        let mut satc = Satc::new(0).atc_required();
+        satc.add_device_scope(DeviceScope::pci_endpoint(0, &[(2, 0)]));
+        dmar.add_satc(satc);
+
+        let bytes = check_table(&dmar);
+        let rmrr_offset = 48 + 24 + 16;
+        let atsr_offset = rmrr_offset + 32;
+        let rhsa_offset = atsr_offset + 16;
+        let satc_offset = rhsa_offset + 20;
+        assert_eq!(bytes.len(), satc_offset + 16);
+
+        assert_eq!(bytes[rmrr_offset..rmrr_offset + 2], [1, 0]);
+        assert_eq!(
+            bytes[rmrr_offset + 16..rmrr_offset + 24],
+            0x7fff_ffffu64.to_le_bytes()
+        );
+        assert_eq!(bytes[rmrr_offset + 24], 1);
+        assert_eq!(bytes[atsr_offset..atsr_offset + 4], [2, 0, 16, 0]);
+        assert_eq!(bytes[atsr_offset + 8], 2);
+        assert_eq!(
+            bytes[rhsa_offset..rhsa_offset + 20],
+            [3, 0, 20, 0, 0, 0, 0, 0, 0x00, 0x00, 0xd9, 0xfe, 0, 0, 0, 0, 1, 0, 0, 0]
+        );
+        assert_eq!(bytes[satc_offset..satc_offset + 5], [5, 0, 16, 0, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_structure_order() {
+        let mut dmar = DMAR::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef, 48);
+        dmar.add_rhsa(Rhsa::new(0xfed9_0000, 1));
+        dmar.add_drhd(Drhd::new(0, 0xfed9_0000).include_pci_all());
+    }
+}
@@ -0,0 +1,342 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    aml::{Name, Package, PackageBuilder, Path, Uuid},
+    Aml, AmlSink,
+};
+
+/// Device Properties UUID, see "Device Properties UUID For _DSD"
+pub const DEVICE_PROPERTIES_UUID: &str = "daffd814-6eba-4d8c-8a91-bc9bbf4aa301";
+/// Hierarchical Data Extension UUID, see "Hierarchical Data Extension
+/// UUID For _DSD"
+pub const HIERARCHICAL_DATA_EXTENSION_UUID: &str = "dbb8e3e6-5886-4ba6-8795-1319f52a966b";
+
+/// Value of a device property
+pub enum PropertyValue {
+    Integer(u64),
+    String(String),
+    /// Reference to another object in the namespace
+    Reference(Path),
+    /// Package of values, e.g. an array of integers or a list of
+    /// references with arguments
+    Package(Vec<PropertyValue>),
+}
+
+impl Aml for PropertyValue {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        match self {
+            PropertyValue::Integer(value) => value.to_aml_bytes(sink),
+            PropertyValue::String(value) => value.to_aml_bytes(sink),
+            PropertyValue::Reference(path) => path.to_aml_bytes(sink),
+            PropertyValue::Package(values) => {
+                let mut package = PackageBuilder::new();
+                for value in values {
+                    package.add_element(value);
+                }
+                package.to_aml_bytes(sink);
+            }
+        }
+    }
+}
+
+macro_rules! integer_property {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for PropertyValue {
+                fn from(value: $ty) -> Self {
+                    PropertyValue::Integer(value.into())
+                }
+            }
+        )*
+    };
+}
+
+integer_property!(u8, u16, u32, u64);
+
+impl From<&str> for PropertyValue {
+    fn from(value: &str) -> Self {
+        PropertyValue::String(value.to_string())
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        PropertyValue::String(value)
+    }
+}
+
+impl From<Path> for PropertyValue {
+    fn from(path: Path) -> Self {
+        PropertyValue::Reference(path)
+    }
+}
+
+impl From<Vec<PropertyValue>> for PropertyValue {
+    fn from(values: Vec<PropertyValue>) -> Self {
+        PropertyValue::Package(values)
+    }
+}
+
+struct Property {
+    key: String,
+    value: PropertyValue,
+}
+
+struct ChildNode {
+    key: String,
+    name: String,
+    node: DataNode,
+}
+
+/// A set of device properties and child data nodes. When serialized
+/// on its own it produces the data package that `_DSD`, or the named
+/// object of a child data node, evaluates to.
+#[derive(Default)]
+pub struct DataNode {
+    properties: Vec<Property>,
+    children: Vec<ChildNode>,
+}
+
+impl DataNode {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_property(&mut self, key: &str, value: impl Into<PropertyValue>) {
+        self.properties.push(Property {
+            key: key.to_string(),
+            value: value.into(),
+        });
+    }
+
+    /// Add a child data node. `key` is the name of the link as seen by
+    /// the OS, while `name` is the 4 character name of the object that
+    /// holds the child's data package in the scope of the device. As
+    /// all the descendants end up in that scope, `name` must be unique
+    /// within the whole device, not just within this node.
+    pub fn add_child(&mut self, key: &str, name: &str, node: DataNode) {
+        assert_eq!(name.len(), 4);
+        self.children.push(ChildNode {
+            key: key.to_string(),
+            name: name.to_string(),
+            node,
+        });
+    }
+
+    // Names of the objects of all the descendant data nodes
+    fn child_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        for child in &self.children {
+            names.push(&child.name);
+            child.node.child_names(names);
+        }
+    }
+
+    // Named objects of all the descendant data nodes
+    fn child_objects(&self, sink: &mut dyn AmlSink) {
+        for child in &self.children {
+            Name::new(Path::new(&child.name), &child.node).to_aml_bytes(sink);
+            child.node.child_objects(sink);
+        }
+    }
+}
+
+impl Aml for DataNode {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let mut package = PackageBuilder::new();
+
+        if !self.properties.is_empty() {
+            let mut properties = PackageBuilder::new();
+            for property in &self.properties {
+                properties.add_element(&Package::new(vec![&property.key, &property.value]));
+            }
+            package.add_element(&Uuid::new(DEVICE_PROPERTIES_UUID));
+            package.add_element(&properties);
+        }
+
+        if !self.children.is_empty() {
+            let mut children = PackageBuilder::new();
+            for child in &self.children {
+                children.add_element(&Package::new(vec![&child.key, &child.name]));
+            }
+            package.add_element(&Uuid::new(HIERARCHICAL_DATA_EXTENSION_UUID));
+            package.add_element(&children);
+        }
+
+        package.to_aml_bytes(sink);
+    }
+}
+
+/// `_DSD` (Device Specific Data) object, to be placed in the scope of
+/// a device. The named objects backing the child data nodes are
+/// emitted alongside it.
+pub struct Dsd<'a> {
+    root: &'a DataNode,
+}
+
+impl<'a> Dsd<'a> {
+    pub fn new(root: &'a DataNode) -> Self {
+        let mut names = Vec::new();
+        root.child_names(&mut names);
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[i + 1..].contains(name));
+        }
+        Dsd { root }
+    }
+}
+
+impl Aml for Dsd<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        Name::new("_DSD".into(), self.root).to_aml_bytes(sink);
+        self.root.child_objects(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aml::{Device, EISAName};
+
+    #[test]
+    fn test_properties() {
+        /*
+        Name (_DSD, Package (0x02)  // _DSD: Device-Specific Data
+        {
+            ToUUID ("daffd814-6eba-4d8c-8a91-bc9bbf4aa301") /* Device Properties for _DSD */,
+            Package (0x01)
+            {
+                Package (0x02)
+                {
+                    "abc",
+                    One
+                }
+            }
+        })
+        */
+        let expected = [
+            0x08, 0x5F, 0x44, 0x53, 0x44, 0x12, 0x22, 0x02, 0x11, 0x13, 0x0A, 0x10, 0x14, 0xD8,
+            0xFF, 0xDA, 0xBA, 0x6E, 0x8C, 0x4D, 0x8A, 0x91, 0xBC, 0x9B, 0xBF, 0x4A, 0xA3, 0x01,
+            0x12, 0x0B, 0x01, 0x12, 0x08, 0x02, 0x0D, 0x61, 0x62, 0x63, 0x00, 0x01,
+        ];
+
+        let mut node = DataNode::new();
+        node.add_property("abc", 1u8);
+        let dsd = Dsd::new(&node);
+
+        let mut bytes = Vec::new();
+        dsd.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(dsd.aml_len(), bytes.len());
+    }
+
+    #[test]
+    fn test_empty() {
+        let node = DataNode::new();
+        let mut bytes = Vec::new();
+        Dsd::new(&node).to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, [0x08, 0x5F, 0x44, 0x53, 0x44, 0x12, 0x02, 0x00]);
+    }
+
+    #[test]
+    fn test_hierarchical_data() {
+        let mut endpoint = DataNode::new();
+        endpoint.add_property("reg", 0u32);
+        endpoint.add_property("remote-endpoint", Path::new("\\_SB_.CAM0"));
+
+        let mut port = DataNode::new();
+        port.add_property("reg", 1u32);
+        port.add_child("endpoint@0", "EP00", endpoint);
+
+        let mut root = DataNode::new();
+        root.add_property("clock-frequency", 100_000_000u32);
+        root.add_property("compatible", "vendor,device");
+        root.add_property(
+            "reg",
+            vec![PropertyValue::from(0x1000_0000u64), 0x1000u64.into()],
+        );
+        root.add_child("port@1", "PRT1", port);
+
+        let mut bytes = Vec::new();
+        let dsd = Dsd::new(&root);
+        Device::new(
+            "_SB_.DEV0".into(),
+            vec![&Name::new("_HID".into(), &EISAName::new("PNP0A05")), &dsd],
+        )
+        .to_aml_bytes(&mut bytes);
+
+        let device_properties = Uuid::new(DEVICE_PROPERTIES_UUID);
+        let hierarchical_data = Uuid::new(HIERARCHICAL_DATA_EXTENSION_UUID);
+        let mut expected = Vec::new();
+        Device::new(
+            "_SB_.DEV0".into(),
+            vec![
+                &Name::new("_HID".into(), &EISAName::new("PNP0A05")),
+                &Name::new(
+                    "_DSD".into(),
+                    &Package::new(vec![
+                        &device_properties,
+                        &Package::new(vec![
+                            &Package::new(vec![&"clock-frequency", &100_000_000u32]),
+                            &Package::new(vec![&"compatible", &"vendor,device"]),
+                            &Package::new(vec![
+                                &"reg",
+                                &Package::new(vec![&0x1000_0000u32, &0x1000u32]),
+                            ]),
+                        ]),
+                        &hierarchical_data,
+                        &Package::new(vec![&Package::new(vec![&"port@1", &"PRT1"])]),
+                    ]),
+                ),
+                &Name::new(
+                    "PRT1".into(),
+                    &Package::new(vec![
+                        &device_properties,
+                        &Package::new(vec![&Package::new(vec![&"reg", &1u32])]),
+                        &hierarchical_data,
+                        &Package::new(vec![&Package::new(vec![&"endpoint@0", &"EP00"])]),
+                    ]),
+                ),
+                &Name::new(
+                    "EP00".into(),
+                    &Package::new(vec![
+                        &device_properties,
+                        &Package::new(vec![
+                            &Package::new(vec![&"reg", &0u32]),
+                            &Package::new(vec![&"remote-endpoint", &Path::new("\\_SB_.CAM0")]),
+                        ]),
+                    ]),
+                ),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+
+        assert_eq!(bytes, expected);
+
+        let mut dsd_bytes = Vec::new();
+        dsd.to_aml_bytes(&mut dsd_bytes);
+        assert_eq!(dsd.aml_len(), dsd_bytes.len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_child_name() {
+        let mut port0 = DataNode::new();
+        port0.add_child("endpoint@0", "EP00", DataNode::new());
+        let mut port1 = DataNode::new();
+        port1.add_child("endpoint@0", "EP00", DataNode::new());
+
+        let mut root = DataNode::new();
+        root.add_child("port@0", "PRT0", port0);
+        root.add_child("port@1", "PRT1", port1);
+        Dsd::new(&root);
+    }
+}
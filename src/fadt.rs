@@ -149,6 +149,10 @@ impl Aml for FADT {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
         sink.vec(self.table.as_bytes());
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 impl FADTBuilder {
@@ -262,6 +266,7 @@ mod test {
             .preferred_pm_profile(PmProfile::EnterpriseServer)
             .finalize();
         fadt.to_aml_bytes(&mut bytes);
+        assert_eq!(fadt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -100,4 +100,8 @@ impl Aml for GAS {
         sink.byte(self.access_size as u8);
         sink.qword(self.address.into());
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
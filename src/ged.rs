@@ -0,0 +1,232 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    aml::{
+        And, Device, Equal, Field, FieldAccessType, FieldEntry, FieldLockRule, FieldUpdateRule, If,
+        Interrupt, Local, Method, MethodCall, Name, Notify, OpRegion, OpRegionSpace, Path,
+        ResourceTemplate, Store,
+    },
+    Aml, AmlSink,
+};
+
+/// What the `_EVT` method of the GED does when an event is signaled
+#[derive(Clone)]
+pub enum GedAction {
+    /// Notify an object with the given value
+    Notify(Path, u8),
+    /// Call a method taking no arguments
+    Call(Path),
+}
+
+impl Aml for GedAction {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        match self {
+            GedAction::Notify(object, value) => Notify::new(object, value).to_aml_bytes(sink),
+            GedAction::Call(method) => MethodCall::new(method.clone(), vec![]).to_aml_bytes(sink),
+        }
+    }
+}
+
+// Action of an event, run when its bit is set in the status read
+// into Local0
+struct GedEvent {
+    interrupt: u32,
+    status_bit: u8,
+    action: GedAction,
+}
+
+impl Aml for GedEvent {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let mask = 1u32 << self.status_bit;
+        If::new(
+            &Equal::new(&And::new(&Local(1), &Local(0), &mask), &mask),
+            vec![&self.action],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Generic Event Device (ACPI0013) of hardware-reduced platforms.
+/// Events are signaled through the interrupts of the device, and
+/// identified by a bit in a 32-bit status register that is expected
+/// to clear itself when read. As the register is shared by all the
+/// interrupts, `_EVT` runs the action of every bit set in it, whatever
+/// the interrupt that fired.
+pub struct Ged {
+    path: Path,
+    status_address: u64,
+    events: Vec<GedEvent>,
+}
+
+impl Ged {
+    /// Create a GED whose status register is at `status_address` in
+    /// system memory.
+    pub fn new(path: Path, status_address: u64) -> Self {
+        Ged {
+            path,
+            status_address,
+            events: Vec::new(),
+        }
+    }
+
+    /// Run `action` when `status_bit` is set in the status register,
+    /// `interrupt` being the one raised along with it. Each status bit
+    /// can only be used by one event.
+    pub fn add_event(&mut self, interrupt: u32, status_bit: u8, action: GedAction) {
+        assert!(status_bit < 32);
+        assert!(!self
+            .events
+            .iter()
+            .any(|event| event.status_bit == status_bit));
+        self.events.push(GedEvent {
+            interrupt,
+            status_bit,
+            action,
+        });
+    }
+
+    // Interrupts of the device, in the order of their first event
+    fn interrupts(&self) -> Vec<u32> {
+        let mut interrupts = Vec::new();
+        for event in &self.events {
+            if !interrupts.contains(&event.interrupt) {
+                interrupts.push(event.interrupt);
+            }
+        }
+        interrupts
+    }
+}
+
+impl Aml for Ged {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let interrupts = self.interrupts();
+
+        let resources: Vec<Interrupt> = interrupts
+            .iter()
+            .map(|interrupt| Interrupt::new(true, true, false, false, *interrupt))
+            .collect();
+
+        let hid = Name::new("_HID".into(), &"ACPI0013");
+        let uid = Name::new("_UID".into(), &0u8);
+        let crs = Name::new(
+            "_CRS".into(),
+            &ResourceTemplate::new(resources.iter().map(|r| r as &dyn Aml).collect()),
+        );
+        let region = OpRegion::new(
+            "GDST".into(),
+            OpRegionSpace::SystemMemory,
+            &self.status_address,
+            &4u8,
+        );
+        let field = Field::new(
+            "GDST".into(),
+            FieldAccessType::DWord,
+            FieldLockRule::NoLock,
+            FieldUpdateRule::WriteAsZeroes,
+            vec![FieldEntry::Named(*b"GDAT", 32)],
+        );
+        let status = Path::new("GDAT");
+        let read_status = Store::new(&Local(0), &status);
+        let mut evt_body: Vec<&dyn Aml> = vec![&read_status];
+        evt_body.extend(self.events.iter().map(|event| event as &dyn Aml));
+        let evt = Method::new("_EVT".into(), 1, true, evt_body);
+
+        Device::new(
+            self.path.clone(),
+            vec![&hid, &uid, &crs, &region, &field, &evt],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ged() {
+        let mut ged = Ged::new("_SB_.GED_".into(), 0xfed0_0000);
+        ged.add_event(40, 0, GedAction::Call("_SB_.CPUS.CSCN".into()));
+        ged.add_event(40, 1, GedAction::Call("_SB_.MHPC.MSCN".into()));
+        ged.add_event(41, 2, GedAction::Notify("_SB_.PWRB".into(), 0x80));
+
+        let mut bytes = Vec::new();
+        ged.to_aml_bytes(&mut bytes);
+        assert_eq!(ged.aml_len(), bytes.len());
+
+        let cpu_scan = MethodCall::new("_SB_.CPUS.CSCN".into(), vec![]);
+        let memory_scan = MethodCall::new("_SB_.MHPC.MSCN".into(), vec![]);
+        let power_button = Path::new("_SB_.PWRB");
+        let mut expected = Vec::new();
+        Device::new(
+            "_SB_.GED_".into(),
+            vec![
+                &Name::new("_HID".into(), &"ACPI0013"),
+                &Name::new("_UID".into(), &0u8),
+                &Name::new(
+                    "_CRS".into(),
+                    &ResourceTemplate::new(vec![
+                        &Interrupt::new(true, true, false, false, 40),
+                        &Interrupt::new(true, true, false, false, 41),
+                    ]),
+                ),
+                &OpRegion::new(
+                    "GDST".into(),
+                    OpRegionSpace::SystemMemory,
+                    &0xfed0_0000u64,
+                    &4u8,
+                ),
+                &Field::new(
+                    "GDST".into(),
+                    FieldAccessType::DWord,
+                    FieldLockRule::NoLock,
+                    FieldUpdateRule::WriteAsZeroes,
+                    vec![FieldEntry::Named(*b"GDAT", 32)],
+                ),
+                &Method::new(
+                    "_EVT".into(),
+                    1,
+                    true,
+                    vec![
+                        &Store::new(&Local(0), &Path::new("GDAT")),
+                        &If::new(
+                            &Equal::new(&And::new(&Local(1), &Local(0), &1u32), &1u32),
+                            vec![&cpu_scan],
+                        ),
+                        &If::new(
+                            &Equal::new(&And::new(&Local(1), &Local(0), &2u32), &2u32),
+                            vec![&memory_scan],
+                        ),
+                        &If::new(
+                            &Equal::new(&And::new(&Local(1), &Local(0), &4u32), &4u32),
+                            vec![&Notify::new(&power_button, &0x80u8)],
+                        ),
+                    ],
+                ),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_status_bit() {
+        let mut ged = Ged::new("GED0".into(), 0);
+        ged.add_event(40, 32, GedAction::Call("SCAN".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_status_bit() {
+        let mut ged = Ged::new("GED0".into(), 0);
+        ged.add_event(40, 0, GedAction::Call("SCAN".into()));
+        ged.add_event(41, 0, GedAction::Call("SCN2".into()));
+    }
+}
@@ -0,0 +1,451 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::{
+    byteorder::{self, LE},
+    Immutable, IntoBytes,
+};
+
+extern crate alloc;
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{aml_as_bytes, assert_same_size, u8sum, Aml, AmlSink, Checksum, TableHeader};
+
+type U16 = byteorder::U16<LE>;
+type U32 = byteorder::U32<LE>;
+type U64 = byteorder::U64<LE>;
+
+// Value of the CntControlBase and CntReadBase fields when the frame is
+// not provided
+const NOT_PROVIDED: u64 = u64::MAX;
+
+#[repr(u8)]
+enum PlatformTimerType {
+    GtBlock = 0,
+    Watchdog = 1,
+}
+
+/// Architected timer whose interrupt is described by the GTDT
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Timer {
+    SecureEl1,
+    NonSecureEl1,
+    VirtualEl1,
+    NonSecureEl2,
+    VirtualEl2,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum TimerFlags {
+    EdgeTriggered = 1 << 0,
+    ActiveLow = 1 << 1,
+    // NOTE: Only valid for the architected timers
+    AlwaysOn = 1 << 2,
+}
+
+fn timer_flags(flags: &[TimerFlags]) -> u32 {
+    flags.iter().fold(0, |acc, flag| acc | *flag as u32)
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Default, IntoBytes, Immutable)]
+struct Body {
+    header: TableHeader,
+    cnt_control_base: U64,
+    _reserved: U32,
+    secure_el1_gsiv: U32,
+    secure_el1_flags: U32,
+    non_secure_el1_gsiv: U32,
+    non_secure_el1_flags: U32,
+    virtual_el1_gsiv: U32,
+    virtual_el1_flags: U32,
+    non_secure_el2_gsiv: U32,
+    non_secure_el2_flags: U32,
+    cnt_read_base: U64,
+    platform_timer_count: U32,
+    platform_timer_offset: U32,
+    virtual_el2_gsiv: U32,
+    virtual_el2_flags: U32,
+}
+
+assert_same_size!(Body, [u8; 104]);
+
+impl Body {
+    fn len() -> usize {
+        core::mem::size_of::<Self>()
+    }
+}
+
+/// The Generic Timer Description Table describes the interrupts of
+/// the architected timers of ARM processors, along with the memory
+/// mapped timers and watchdogs of the platform.
+pub struct GTDT {
+    body: Body,
+    checksum: Checksum,
+    platform_timers: Vec<Box<dyn Aml>>,
+}
+
+impl GTDT {
+    pub fn new(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
+        let body = Body {
+            header: TableHeader {
+                signature: *b"GTDT",
+                length: (Body::len() as u32).into(),
+                revision: 3,
+                checksum: 0,
+                oem_id,
+                oem_table_id,
+                oem_revision: oem_revision.into(),
+                creator_id: crate::CREATOR_ID,
+                creator_revision: crate::CREATOR_REVISION,
+            },
+            cnt_control_base: NOT_PROVIDED.into(),
+            cnt_read_base: NOT_PROVIDED.into(),
+            ..Default::default()
+        };
+
+        let mut checksum = Checksum::default();
+        checksum.append(body.as_bytes());
+
+        let mut gtdt = Self {
+            body,
+            checksum,
+            platform_timers: Vec::new(),
+        };
+        gtdt.body.header.checksum = gtdt.checksum.value();
+        gtdt
+    }
+
+    // Apply `f` to the fixed part of the table, keeping the checksum
+    // up to date. The checksum tracks the sum of the table with its
+    // checksum field cleared.
+    fn update_body(&mut self, f: impl FnOnce(&mut Body)) {
+        self.body.header.checksum = 0;
+        self.checksum.delete(self.body.as_bytes());
+        f(&mut self.body);
+        self.checksum.append(self.body.as_bytes());
+        self.body.header.checksum = self.checksum.value();
+    }
+
+    /// Physical address of the CNTControlBase frame of the system
+    /// counter
+    pub fn cnt_control_base(mut self, address: u64) -> Self {
+        self.update_body(|body| body.cnt_control_base = address.into());
+        self
+    }
+
+    /// Physical address of the CNTReadBase frame of the system counter
+    pub fn cnt_read_base(mut self, address: u64) -> Self {
+        self.update_body(|body| body.cnt_read_base = address.into());
+        self
+    }
+
+    pub fn timer(mut self, timer: Timer, gsiv: u32, flags: &[TimerFlags]) -> Self {
+        let gsiv = gsiv.into();
+        let flags = timer_flags(flags).into();
+        self.update_body(|body| match timer {
+            Timer::SecureEl1 => {
+                body.secure_el1_gsiv = gsiv;
+                body.secure_el1_flags = flags;
+            }
+            Timer::NonSecureEl1 => {
+                body.non_secure_el1_gsiv = gsiv;
+                body.non_secure_el1_flags = flags;
+            }
+            Timer::VirtualEl1 => {
+                body.virtual_el1_gsiv = gsiv;
+                body.virtual_el1_flags = flags;
+            }
+            Timer::NonSecureEl2 => {
+                body.non_secure_el2_gsiv = gsiv;
+                body.non_secure_el2_flags = flags;
+            }
+            Timer::VirtualEl2 => {
+                body.virtual_el2_gsiv = gsiv;
+                body.virtual_el2_flags = flags;
+            }
+        });
+        self
+    }
+
+    fn add_platform_timer(&mut self, sum: u8, len: u32, timer: Box<dyn Aml>) {
+        self.update_body(|body| {
+            body.header.length = (body.header.length.get() + len).into();
+            body.platform_timer_count = (body.platform_timer_count.get() + 1).into();
+            body.platform_timer_offset = (Body::len() as u32).into();
+        });
+        self.checksum.add(sum);
+        self.body.header.checksum = self.checksum.value();
+        self.platform_timers.push(timer);
+    }
+
+    pub fn add_gt_block(&mut self, block: GtBlock) {
+        self.add_platform_timer(u8sum(&block), block.len() as u32, Box::new(block));
+    }
+
+    pub fn add_watchdog(&mut self, watchdog: Watchdog) {
+        self.add_platform_timer(u8sum(&watchdog), Watchdog::len() as u32, Box::new(watchdog));
+    }
+}
+
+impl Aml for GTDT {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.vec(self.body.as_bytes());
+
+        for timer in &self.platform_timers {
+            timer.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.body.header.length.get() as usize
+    }
+}
+
+#[repr(u32)]
+enum GtFrameFlags {
+    Secure = 1 << 0,
+    AlwaysOn = 1 << 1,
+}
+
+/// Timer frame of a GT Block
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Default, IntoBytes, Immutable)]
+pub struct GtFrame {
+    frame_number: u8,
+    _reserved: [u8; 3],
+    cnt_base: U64,
+    cnt_el0_base: U64,
+    physical_gsiv: U32,
+    physical_flags: U32,
+    virtual_gsiv: U32,
+    virtual_flags: U32,
+    common_flags: U32,
+}
+
+assert_same_size!(GtFrame, [u8; 40]);
+aml_as_bytes!(GtFrame);
+
+impl GtFrame {
+    fn len() -> usize {
+        core::mem::size_of::<Self>()
+    }
+
+    /// Frame `frame_number` of the block, whose CNTBaseN frame is at
+    /// `cnt_base`
+    pub fn new(frame_number: u8, cnt_base: u64) -> Self {
+        assert!(frame_number < 8);
+        Self {
+            frame_number,
+            cnt_base: cnt_base.into(),
+            cnt_el0_base: NOT_PROVIDED.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Physical address of the CNTEL0BaseN frame
+    pub fn cnt_el0_base(mut self, address: u64) -> Self {
+        self.cnt_el0_base = address.into();
+        self
+    }
+
+    pub fn physical_timer(mut self, gsiv: u32, flags: &[TimerFlags]) -> Self {
+        assert!(!flags.contains(&TimerFlags::AlwaysOn));
+        self.physical_gsiv = gsiv.into();
+        self.physical_flags = timer_flags(flags).into();
+        self
+    }
+
+    pub fn virtual_timer(mut self, gsiv: u32, flags: &[TimerFlags]) -> Self {
+        assert!(!flags.contains(&TimerFlags::AlwaysOn));
+        self.virtual_gsiv = gsiv.into();
+        self.virtual_flags = timer_flags(flags).into();
+        self
+    }
+
+    pub fn secure(mut self) -> Self {
+        self.common_flags = (self.common_flags.get() | GtFrameFlags::Secure as u32).into();
+        self
+    }
+
+    pub fn always_on(mut self) -> Self {
+        self.common_flags = (self.common_flags.get() | GtFrameFlags::AlwaysOn as u32).into();
+        self
+    }
+}
+
+/// Memory-mapped generic timer block, made of up to 8 timer frames
+pub struct GtBlock {
+    address: u64,
+    frames: Vec<GtFrame>,
+}
+
+impl GtBlock {
+    const HEADER_LEN: usize = 20;
+
+    /// Block whose CNTCTLBase frame is at `address`
+    pub fn new(address: u64) -> Self {
+        Self {
+            address,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn add_frame(&mut self, frame: GtFrame) {
+        assert!(self.frames.len() < 8);
+        self.frames.push(frame);
+    }
+
+    fn len(&self) -> usize {
+        Self::HEADER_LEN + self.frames.len() * GtFrame::len()
+    }
+}
+
+impl Aml for GtBlock {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.byte(PlatformTimerType::GtBlock as u8);
+        sink.word(self.len() as u16);
+        sink.byte(0); // reserved
+        sink.qword(self.address);
+        sink.dword(self.frames.len() as u32);
+        sink.dword(Self::HEADER_LEN as u32);
+
+        for frame in &self.frames {
+            frame.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum WatchdogFlags {
+    EdgeTriggered = 1 << 0,
+    ActiveLow = 1 << 1,
+    Secure = 1 << 2,
+}
+
+/// Arm SBSA Generic Watchdog
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Default, IntoBytes, Immutable)]
+pub struct Watchdog {
+    r#type: u8,
+    length: U16,
+    _reserved: u8,
+    refresh_frame: U64,
+    control_frame: U64,
+    gsiv: U32,
+    flags: U32,
+}
+
+assert_same_size!(Watchdog, [u8; 28]);
+aml_as_bytes!(Watchdog);
+
+impl Watchdog {
+    fn len() -> usize {
+        core::mem::size_of::<Self>()
+    }
+
+    pub fn new(refresh_frame: u64, control_frame: u64, gsiv: u32) -> Self {
+        Self {
+            r#type: PlatformTimerType::Watchdog as u8,
+            length: (Self::len() as u16).into(),
+            refresh_frame: refresh_frame.into(),
+            control_frame: control_frame.into(),
+            gsiv: gsiv.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn flag(mut self, flag: WatchdogFlags) -> Self {
+        self.flags = (self.flags.get() | flag as u32).into();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_table;
+
+    #[test]
+    fn test_gtdt() {
+        let gtdt = GTDT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef)
+            .timer(Timer::SecureEl1, 29, &[TimerFlags::ActiveLow])
+            .timer(Timer::NonSecureEl1, 30, &[TimerFlags::AlwaysOn])
+            .timer(Timer::VirtualEl1, 27, &[])
+            .timer(Timer::NonSecureEl2, 26, &[])
+            .timer(Timer::VirtualEl2, 28, &[TimerFlags::EdgeTriggered]);
+        let bytes = check_table(&gtdt);
+        assert_eq!(bytes.len(), 104);
+        assert_eq!(bytes[0..4], *b"GTDT");
+        assert_eq!(bytes[8], 3);
+
+        // CntControlBase, not provided
+        assert_eq!(bytes[36..44], [0xff; 8]);
+        // Secure EL1 timer
+        assert_eq!(bytes[48..56], [29, 0, 0, 0, 2, 0, 0, 0]);
+        // Non-secure EL1 timer
+        assert_eq!(bytes[56..64], [30, 0, 0, 0, 4, 0, 0, 0]);
+        // No platform timer
+        assert_eq!(bytes[88..96], [0; 8]);
+        // Virtual EL2 timer
+        assert_eq!(bytes[96..104], [28, 0, 0, 0, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_platform_timers() {
+        let mut gtdt = GTDT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef)
+            .cnt_control_base(0x2a43_0000)
+            .cnt_read_base(0x2a80_0000);
+        check_table(&gtdt);
+
+        let mut block = GtBlock::new(0x2a81_0000);
+        block.add_frame(
+            GtFrame::new(0, 0x2a82_0000)
+                .physical_timer(92, &[TimerFlags::EdgeTriggered])
+                .virtual_timer(93, &[])
+                .always_on(),
+        );
+        block.add_frame(GtFrame::new(1, 0x2a83_0000).secure());
+        gtdt.add_gt_block(block);
+        check_table(&gtdt);
+
+        gtdt.add_watchdog(
+            Watchdog::new(0x2a44_0000, 0x2a45_0000, 93).flag(WatchdogFlags::EdgeTriggered),
+        );
+        let bytes = check_table(&gtdt);
+        assert_eq!(bytes.len(), 104 + 20 + 2 * 40 + 28);
+        // Platform timer count and offset
+        assert_eq!(bytes[88..96], [2, 0, 0, 0, 104, 0, 0, 0]);
+
+        // GT Block header
+        assert_eq!(
+            bytes[104..124],
+            [0, 100, 0, 0, 0x00, 0x00, 0x81, 0x2a, 0, 0, 0, 0, 2, 0, 0, 0, 20, 0, 0, 0]
+        );
+        // Common flags of the frames
+        assert_eq!(bytes[160..164], [2, 0, 0, 0]);
+        assert_eq!(bytes[200..204], [1, 0, 0, 0]);
+        // Watchdog
+        assert_eq!(
+            bytes[204..],
+            [
+                1, 28, 0, 0, 0x00, 0x00, 0x44, 0x2a, 0, 0, 0, 0, 0x00, 0x00, 0x45, 0x2a, 0, 0, 0,
+                0, 93, 0, 0, 0, 1, 0, 0, 0
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_always_on_frame_timer() {
+        GtFrame::new(0, 0x2a82_0000).physical_timer(92, &[TimerFlags::AlwaysOn]);
+    }
+}
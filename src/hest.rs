@@ -94,6 +94,10 @@ impl Aml for HEST {
             st.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 /// PCIe root ports may implement Advanced Error Reporting support.
@@ -600,6 +604,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         hest.to_aml_bytes(&mut bytes);
+        assert_eq!(hest.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), TableHeader::len() + 4);
@@ -624,6 +629,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         hest.to_aml_bytes(&mut bytes);
+        assert_eq!(hest.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), TableHeader::len() + 4 + PcieAerRootPort::len());
@@ -647,6 +653,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         hest.to_aml_bytes(&mut bytes);
+        assert_eq!(hest.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), TableHeader::len() + 4 + PcieAerDevice::len());
@@ -671,6 +678,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         hest.to_aml_bytes(&mut bytes);
+        assert_eq!(hest.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), TableHeader::len() + 4 + PcieAerBridge::len());
@@ -708,6 +716,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         hest.to_aml_bytes(&mut bytes);
+        assert_eq!(hest.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes[0..4], *b"HEST");
@@ -753,6 +762,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         hest.to_aml_bytes(&mut bytes);
+        assert_eq!(hest.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes[0..4], *b"HEST");
@@ -98,6 +98,10 @@ impl Aml for HMAT {
             entry.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 // This structure describes the system physical address range occupied
@@ -275,6 +279,10 @@ impl Aml for SystemLocality {
             sink.word(*entry);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 pub struct MemorySideCache {
@@ -355,6 +363,10 @@ impl Aml for MemorySideCache {
             sink.word(*handle);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 #[cfg(test)]
@@ -364,6 +376,7 @@ mod tests {
     fn check_checksum(hmat: &HMAT) {
         let mut bytes = Vec::new();
         hmat.to_aml_bytes(&mut bytes);
+        assert_eq!(hmat.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -371,6 +384,7 @@ mod tests {
     fn get_size(hmat: &HMAT) -> usize {
         let mut bytes = Vec::new();
         hmat.to_aml_bytes(&mut bytes);
+        assert_eq!(hmat.aml_len(), bytes.len());
         bytes.len()
     }
 
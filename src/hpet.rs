@@ -0,0 +1,237 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::{
+    byteorder::{self, LE},
+    Immutable, IntoBytes,
+};
+
+extern crate alloc;
+use alloc::{format, vec};
+
+use crate::{
+    aml::{Device, EISAName, Memory32Fixed, Name, Path, ResourceTemplate},
+    gas::{AddressSpace, GAS},
+    Aml, AmlSink, TableHeader,
+};
+
+type U16 = byteorder::U16<LE>;
+type U32 = byteorder::U32<LE>;
+
+// Size of the register block of an HPET
+const REGISTERS_LEN: u32 = 0x400;
+
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum PageProtection {
+    #[default]
+    None = 0,
+    Protected4K = 1,
+    Protected64K = 2,
+}
+
+/// The HPET table describes an event timer block of the High
+/// Precision Event Timer, the matching `Device` being given by
+/// [`HPET::device`].
+#[derive(Copy, Clone, Default, IntoBytes, Immutable)]
+#[repr(C, packed)]
+pub struct HPET {
+    header: TableHeader,
+    event_timer_block_id: U32,
+    base_address: GAS,
+    hpet_number: u8,
+    min_clock_tick: U16,
+    page_protection: u8,
+}
+
+crate::assert_same_size!(HPET, [u8; 56]);
+
+impl HPET {
+    /// `event_timer_block_id` is the low half of the General
+    /// Capabilities and ID register of the block, whose registers are
+    /// at `base_address` in the system memory
+    pub fn new(
+        oem_id: [u8; 6],
+        oem_table_id: [u8; 8],
+        oem_revision: u32,
+        event_timer_block_id: u32,
+        base_address: GAS,
+    ) -> Self {
+        assert!(matches!(
+            base_address.address_space_id,
+            AddressSpace::SystemMemory
+        ));
+
+        let header = TableHeader {
+            signature: *b"HPET",
+            length: (Self::len() as u32).into(),
+            revision: 1,
+            checksum: 0,
+            oem_id,
+            oem_table_id,
+            oem_revision: oem_revision.into(),
+            creator_id: crate::CREATOR_ID,
+            creator_revision: crate::CREATOR_REVISION,
+        };
+
+        Self {
+            header,
+            event_timer_block_id: event_timer_block_id.into(),
+            base_address,
+            ..Default::default()
+        }
+        .update_header()
+    }
+
+    fn update_header(mut self) -> Self {
+        self.header.checksum = 0;
+        self.header.checksum = crate::generate_checksum(self.as_bytes());
+        self
+    }
+
+    /// Sequence number of the block, starting from 0
+    pub fn hpet_number(mut self, hpet_number: u8) -> Self {
+        self.hpet_number = hpet_number;
+        self.update_header()
+    }
+
+    /// Minimum number of clock ticks without lost interrupts in
+    /// periodic mode
+    pub fn min_clock_tick(mut self, min_clock_tick: u16) -> Self {
+        self.min_clock_tick = min_clock_tick.into();
+        self.update_header()
+    }
+
+    pub fn page_protection(mut self, page_protection: PageProtection) -> Self {
+        self.page_protection = page_protection as u8;
+        self.update_header()
+    }
+
+    pub fn len() -> usize {
+        core::mem::size_of::<Self>()
+    }
+
+    /// `Device (HPET)` describing the same event timer block in the
+    /// namespace
+    pub fn device(&self) -> HpetDevice {
+        let address = self.base_address.address.get();
+        assert!(address + REGISTERS_LEN as u64 <= u32::MAX as u64 + 1);
+
+        HpetDevice {
+            hpet_number: self.hpet_number,
+            address: address as u32,
+        }
+    }
+}
+
+crate::aml_as_bytes!(HPET);
+
+/// `Device (HPET)` of an event timer block, with its registers as the
+/// only resource. The other blocks are named `HPxx` after their
+/// `hpet_number`, in hexadecimal.
+pub struct HpetDevice {
+    hpet_number: u8,
+    address: u32,
+}
+
+impl Aml for HpetDevice {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let registers = Memory32Fixed::new(false, self.address, REGISTERS_LEN);
+        let name = if self.hpet_number == 0 {
+            "HPET".into()
+        } else {
+            format!("HP{:02X}", self.hpet_number)
+        };
+        Device::new(
+            Path::new(&name),
+            vec![
+                &Name::new("_HID".into(), &EISAName::new("PNP0103")),
+                &Name::new("_UID".into(), &self.hpet_number),
+                &Name::new("_CRS".into(), &ResourceTemplate::new(vec![&registers])),
+            ],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gas::AccessSize, test_utils::check_table};
+    use alloc::vec::Vec;
+
+    fn hpet() -> HPET {
+        HPET::new(
+            *b"FOOBAR",
+            *b"CAFEDEAD",
+            0xdead_beef,
+            0x8086_a201,
+            GAS::new(
+                AddressSpace::SystemMemory,
+                0,
+                0,
+                AccessSize::Undefined,
+                0xfed0_0000,
+            ),
+        )
+    }
+
+    #[test]
+    fn test_hpet() {
+        let hpet = hpet()
+            .min_clock_tick(0x80)
+            .page_protection(PageProtection::Protected4K);
+
+        let bytes = check_table(&hpet);
+
+        assert_eq!(bytes[0..4], *b"HPET");
+        assert_eq!(bytes[4], 56);
+        assert_eq!(
+            bytes[36..],
+            [
+                0x01, 0xa2, 0x86, 0x80, // event timer block ID
+                0, 0, 0, 0, 0x00, 0x00, 0xd0, 0xfe, 0, 0, 0, 0, // base address
+                0, 0x80, 0, 1
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hpet_device() {
+        /*
+        Device (HPET)
+        {
+            Name (_HID, EisaId ("PNP0103"))
+            Name (_UID, Zero)
+            Name (_CRS, ResourceTemplate ()
+            {
+                Memory32Fixed (ReadOnly,
+                    0xFED00000,
+                    0x00000400,
+                    )
+            })
+        }
+        */
+        let expected = [
+            0x5b, 0x82, 0x2c, 0x48, 0x50, 0x45, 0x54, 0x08, 0x5f, 0x48, 0x49, 0x44, 0x0c, 0x41,
+            0xd0, 0x01, 0x03, 0x08, 0x5f, 0x55, 0x49, 0x44, 0x00, 0x08, 0x5f, 0x43, 0x52, 0x53,
+            0x11, 0x11, 0x0a, 0x0e, 0x86, 0x09, 0x00, 0x00, 0x00, 0x00, 0xd0, 0xfe, 0x00, 0x04,
+            0x00, 0x00, 0x79, 0x00,
+        ];
+        let device = hpet().device();
+        let mut bytes = Vec::new();
+        device.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(device.aml_len(), bytes.len());
+    }
+
+    #[test]
+    fn test_second_hpet_device() {
+        let device = hpet().hpet_number(0x1a).device();
+        let mut bytes = Vec::new();
+        device.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes[3..7], *b"HP1A");
+    }
+}
@@ -0,0 +1,999 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::IntoBytes;
+
+extern crate alloc;
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{madt::Trigger, Aml, AmlSink, Checksum, TableHeader};
+
+/// Handle of a node added to an [`IORT`], used by the ID mappings
+/// and the PMCG nodes to refer to it. The offset of the node is only
+/// resolved when the table is serialized, and the handle is only
+/// meaningful to the table that returned it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NodeHandle(usize);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum NodeType {
+    ItsGroup = 0,
+    NamedComponent = 1,
+    RootComplex = 2,
+    Smmu = 3,
+    Smmuv3 = 4,
+    Pmcg = 5,
+    Rmr = 6,
+}
+
+trait Node {
+    fn node_type(&self) -> NodeType;
+    fn revision(&self) -> u8;
+    // Length of the node specific data, between the common header and
+    // the ID mappings
+    fn data_len(&self) -> usize;
+    // `offsets` gives the offset of each node of the table, indexed by
+    // handle
+    fn write_data(&self, offsets: &[u32], sink: &mut dyn AmlSink);
+}
+
+struct Entry {
+    node: Box<dyn Node>,
+    id_mappings: Vec<IdMapping>,
+}
+
+impl Entry {
+    const HEADER_LEN: usize = 16;
+
+    fn len(&self) -> usize {
+        Self::HEADER_LEN + self.node.data_len() + self.id_mappings.len() * IdMapping::len()
+    }
+
+    fn to_aml_bytes(&self, identifier: u32, offsets: &[u32], sink: &mut dyn AmlSink) {
+        let mappings_offset = if self.id_mappings.is_empty() {
+            0
+        } else {
+            Self::HEADER_LEN + self.node.data_len()
+        };
+
+        sink.byte(self.node.node_type() as u8);
+        sink.word(self.len() as u16);
+        sink.byte(self.node.revision());
+        sink.dword(identifier);
+        sink.dword(self.id_mappings.len() as u32);
+        sink.dword(mappings_offset as u32);
+        self.node.write_data(offsets, sink);
+
+        for mapping in &self.id_mappings {
+            mapping.to_aml_bytes(offsets, sink);
+        }
+    }
+}
+
+/// The IO Remapping Table describes the topology of the IO of ARM
+/// systems, that is how the IDs of the requesters behind root
+/// complexes and named components are mapped through the SMMUs up to
+/// the ITS groups. It follows issue E.d of the specification.
+pub struct IORT {
+    header: TableHeader,
+    nodes: Vec<Entry>,
+}
+
+impl IORT {
+    const NODE_OFFSET: u32 = 48;
+
+    pub fn new(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
+        let header = TableHeader {
+            signature: *b"IORT",
+            length: Self::NODE_OFFSET.into(),
+            revision: 5,
+            checksum: 0,
+            oem_id,
+            oem_table_id,
+            oem_revision: oem_revision.into(),
+            creator_id: crate::CREATOR_ID,
+            creator_revision: crate::CREATOR_REVISION,
+        };
+
+        Self {
+            header,
+            nodes: Vec::new(),
+        }
+    }
+
+    fn add_node(&mut self, node: Box<dyn Node>) -> NodeHandle {
+        let entry = Entry {
+            node,
+            id_mappings: Vec::new(),
+        };
+        let length = self.header.length.get() + entry.len() as u32;
+        self.header.length.set(length);
+        self.nodes.push(entry);
+        NodeHandle(self.nodes.len() - 1)
+    }
+
+    pub fn add_its_group(&mut self, its_group: ItsGroup) -> NodeHandle {
+        self.add_node(Box::new(its_group))
+    }
+
+    pub fn add_named_component(&mut self, component: NamedComponent) -> NodeHandle {
+        self.add_node(Box::new(component))
+    }
+
+    pub fn add_root_complex(&mut self, root_complex: RootComplex) -> NodeHandle {
+        self.add_node(Box::new(root_complex))
+    }
+
+    pub fn add_smmu(&mut self, smmu: Smmu) -> NodeHandle {
+        self.add_node(Box::new(smmu))
+    }
+
+    pub fn add_smmuv3(&mut self, smmu: Smmuv3) -> NodeHandle {
+        self.add_node(Box::new(smmu))
+    }
+
+    pub fn add_pmcg(&mut self, pmcg: Pmcg) -> NodeHandle {
+        self.check_handle(pmcg.node);
+        self.add_node(Box::new(pmcg))
+    }
+
+    pub fn add_rmr(&mut self, rmr: Rmr) -> NodeHandle {
+        self.add_node(Box::new(rmr))
+    }
+
+    fn check_handle(&self, handle: NodeHandle) {
+        assert!(
+            handle.0 < self.nodes.len(),
+            "node handle {} does not belong to this IORT",
+            handle.0
+        );
+    }
+
+    /// Map IDs generated by `node` to the node referenced by
+    /// `mapping`, which must be an ITS group or an SMMU. The mappings
+    /// of a node are laid out in the order they are added.
+    pub fn add_id_mapping(&mut self, node: NodeHandle, mapping: IdMapping) {
+        self.check_handle(node);
+        self.check_handle(mapping.output);
+        let output_type = self.nodes[mapping.output.0].node.node_type();
+        assert!(matches!(
+            output_type,
+            NodeType::ItsGroup | NodeType::Smmu | NodeType::Smmuv3
+        ));
+
+        let entry = &mut self.nodes[node.0];
+        match entry.node.node_type() {
+            NodeType::ItsGroup => panic!("ITS groups have no ID mappings"),
+            // The only mapping of a PMCG is the one of its MSIs
+            NodeType::Pmcg => assert!(entry.id_mappings.is_empty()),
+            _ => {}
+        }
+        entry.id_mappings.push(mapping);
+
+        let length = self.header.length.get() + IdMapping::len() as u32;
+        self.header.length.set(length);
+    }
+
+    fn offsets(&self) -> Vec<u32> {
+        let mut offset = Self::NODE_OFFSET;
+        self.nodes
+            .iter()
+            .map(|entry| {
+                let node_offset = offset;
+                offset += entry.len() as u32;
+                node_offset
+            })
+            .collect()
+    }
+
+    fn write_nodes(&self, offsets: &[u32], sink: &mut dyn AmlSink) {
+        sink.dword(self.nodes.len() as u32);
+        sink.dword(Self::NODE_OFFSET);
+        sink.dword(0); // reserved
+
+        for (identifier, entry) in self.nodes.iter().enumerate() {
+            entry.to_aml_bytes(identifier as u32, offsets, sink);
+        }
+    }
+}
+
+impl Aml for IORT {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        // The references between nodes are only known now, so is the
+        // checksum
+        let offsets = self.offsets();
+        let mut header = self.header;
+        let mut cksum = Checksum::default();
+        cksum.append(header.as_bytes());
+        self.write_nodes(&offsets, &mut cksum);
+        header.checksum = cksum.value();
+
+        sink.vec(header.as_bytes());
+        self.write_nodes(&offsets, sink);
+    }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
+}
+
+/// Range of IDs of a node mapped to a range of IDs of an ITS group or
+/// an SMMU
+pub struct IdMapping {
+    input_base: u32,
+    count: u32,
+    output_base: u32,
+    output: NodeHandle,
+    single: bool,
+}
+
+impl IdMapping {
+    pub fn new(input_base: u32, count: u32, output_base: u32, output: NodeHandle) -> Self {
+        assert!(count > 0);
+        Self {
+            input_base,
+            count,
+            output_base,
+            output,
+            single: false,
+        }
+    }
+
+    /// Single ID used by the node itself, e.g. for the MSIs of an SMMU
+    /// or a PMCG, rather than by the devices behind it
+    pub fn single(output_id: u32, output: NodeHandle) -> Self {
+        Self {
+            input_base: 0,
+            count: 1,
+            output_base: output_id,
+            output,
+            single: true,
+        }
+    }
+
+    fn len() -> usize {
+        20
+    }
+
+    fn to_aml_bytes(&self, offsets: &[u32], sink: &mut dyn AmlSink) {
+        sink.dword(self.input_base);
+        // The number of IDs in the range minus one
+        sink.dword(self.count - 1);
+        sink.dword(self.output_base);
+        sink.dword(offsets[self.output.0]);
+        sink.dword(self.single as u32);
+    }
+}
+
+/// Group of GIC Interrupt Translation Services, referenced by their
+/// identifier in the MADT
+pub struct ItsGroup {
+    its_ids: Vec<u32>,
+}
+
+impl ItsGroup {
+    pub fn new(its_ids: Vec<u32>) -> Self {
+        assert!(!its_ids.is_empty());
+        Self { its_ids }
+    }
+}
+
+impl Node for ItsGroup {
+    fn node_type(&self) -> NodeType {
+        NodeType::ItsGroup
+    }
+
+    fn revision(&self) -> u8 {
+        1
+    }
+
+    fn data_len(&self) -> usize {
+        4 + 4 * self.its_ids.len()
+    }
+
+    fn write_data(&self, _offsets: &[u32], sink: &mut dyn AmlSink) {
+        sink.dword(self.its_ids.len() as u32);
+        for id in &self.its_ids {
+            sink.dword(*id);
+        }
+    }
+}
+
+// Memory access properties of named components and root complexes
+#[derive(Copy, Clone, Default)]
+struct MemoryAccess {
+    coherent: bool,
+}
+
+impl MemoryAccess {
+    // Coherent path to memory and device attributes
+    // cacheable and inner shareable
+    const CPM: u8 = 1 << 0;
+    const DACS: u8 = 1 << 1;
+
+    fn to_aml_bytes(self, sink: &mut dyn AmlSink) {
+        sink.dword(self.coherent as u32);
+        sink.byte(0); // allocation hints
+        sink.word(0); // reserved
+        sink.byte(if self.coherent {
+            Self::CPM | Self::DACS
+        } else {
+            0
+        });
+    }
+}
+
+/// Device described in the namespace, such as a platform device
+/// behind an SMMU
+pub struct NamedComponent {
+    name: String,
+    address_size_limit: u8,
+    memory_access: MemoryAccess,
+    stall: bool,
+    substream_width: u8,
+}
+
+impl NamedComponent {
+    /// `name` is the full path of the device object in the namespace,
+    /// e.g. `\_SB.DEV0`
+    pub fn new(name: &str, address_size_limit: u8) -> Self {
+        assert!(address_size_limit <= 64);
+        Self {
+            name: name.into(),
+            address_size_limit,
+            memory_access: MemoryAccess::default(),
+            stall: false,
+            substream_width: 0,
+        }
+    }
+
+    pub fn cache_coherent(mut self) -> Self {
+        self.memory_access.coherent = true;
+        self
+    }
+
+    pub fn stall(mut self) -> Self {
+        self.stall = true;
+        self
+    }
+
+    pub fn substream_width(mut self, width: u8) -> Self {
+        assert!(width < 32);
+        self.substream_width = width;
+        self
+    }
+
+    // Offset of the name in the node
+    const NAME_OFFSET: usize = 29;
+}
+
+impl Node for NamedComponent {
+    fn node_type(&self) -> NodeType {
+        NodeType::NamedComponent
+    }
+
+    fn revision(&self) -> u8 {
+        4
+    }
+
+    fn data_len(&self) -> usize {
+        // The name is null terminated and padded so that the ID
+        // mappings are 4-byte aligned
+        (Self::NAME_OFFSET + self.name.len() + 1).next_multiple_of(4) - Entry::HEADER_LEN
+    }
+
+    fn write_data(&self, _offsets: &[u32], sink: &mut dyn AmlSink) {
+        sink.dword(self.stall as u32 | (self.substream_width as u32) << 1);
+        self.memory_access.to_aml_bytes(sink);
+        sink.byte(self.address_size_limit);
+        sink.vec(self.name.as_bytes());
+        let padding = Entry::HEADER_LEN + self.data_len() - Self::NAME_OFFSET - self.name.len();
+        for _ in 0..padding {
+            sink.byte(0);
+        }
+    }
+}
+
+/// PCI root complex of a PCI segment
+pub struct RootComplex {
+    segment: u32,
+    address_size_limit: u8,
+    memory_access: MemoryAccess,
+    ats: bool,
+    pri: bool,
+    pasid_width: Option<u8>,
+}
+
+impl RootComplex {
+    pub fn new(segment: u32, address_size_limit: u8) -> Self {
+        assert!(address_size_limit <= 64);
+        Self {
+            segment,
+            address_size_limit,
+            memory_access: MemoryAccess::default(),
+            ats: false,
+            pri: false,
+            pasid_width: None,
+        }
+    }
+
+    pub fn cache_coherent(mut self) -> Self {
+        self.memory_access.coherent = true;
+        self
+    }
+
+    pub fn ats(mut self) -> Self {
+        self.ats = true;
+        self
+    }
+
+    pub fn pri(mut self) -> Self {
+        self.pri = true;
+        self
+    }
+
+    /// PASIDs of up to `max_width` bits are supported
+    pub fn pasid(mut self, max_width: u8) -> Self {
+        assert!(max_width <= 20);
+        self.pasid_width = Some(max_width);
+        self
+    }
+}
+
+impl Node for RootComplex {
+    fn node_type(&self) -> NodeType {
+        NodeType::RootComplex
+    }
+
+    fn revision(&self) -> u8 {
+        4
+    }
+
+    fn data_len(&self) -> usize {
+        24
+    }
+
+    fn write_data(&self, _offsets: &[u32], sink: &mut dyn AmlSink) {
+        self.memory_access.to_aml_bytes(sink);
+        sink.dword(self.ats as u32 | (self.pri as u32) << 1);
+        sink.dword(self.segment);
+        sink.byte(self.address_size_limit);
+        sink.word(self.pasid_width.unwrap_or(0) as u16);
+        sink.byte(0); // reserved
+        sink.dword(self.pasid_width.is_some() as u32);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum SmmuModel {
+    GenericV1 = 0,
+    GenericV2 = 1,
+    Mmu400 = 2,
+    Mmu500 = 3,
+    Mmu401 = 4,
+    CaviumThunderX = 5,
+}
+
+#[derive(Copy, Clone)]
+struct SmmuInterrupt {
+    gsiv: u32,
+    trigger: Trigger,
+}
+
+impl SmmuInterrupt {
+    fn to_aml_bytes(self, sink: &mut dyn AmlSink) {
+        sink.dword(self.gsiv);
+        sink.dword((self.trigger == Trigger::Edge) as u32);
+    }
+}
+
+/// SMMUv1 or SMMUv2
+pub struct Smmu {
+    model: SmmuModel,
+    base_address: u64,
+    span: u64,
+    dvm: bool,
+    coherent_walk: bool,
+    global_interrupts: [SmmuInterrupt; 2],
+    context_interrupts: Vec<SmmuInterrupt>,
+    pmu_interrupts: Vec<SmmuInterrupt>,
+}
+
+impl Smmu {
+    // Offset of the global interrupts in the node
+    const INTERRUPT_OFFSET: usize = 60;
+
+    /// `global_gsiv` and `config_gsiv` are the SMMU_NSgIrpt and
+    /// SMMU_NSgCfgIrpt interrupts
+    pub fn new(
+        model: SmmuModel,
+        base_address: u64,
+        span: u64,
+        global_gsiv: u32,
+        config_gsiv: u32,
+        trigger: Trigger,
+    ) -> Self {
+        Self {
+            model,
+            base_address,
+            span,
+            dvm: false,
+            coherent_walk: false,
+            global_interrupts: [
+                SmmuInterrupt {
+                    gsiv: global_gsiv,
+                    trigger,
+                },
+                SmmuInterrupt {
+                    gsiv: config_gsiv,
+                    trigger,
+                },
+            ],
+            context_interrupts: Vec::new(),
+            pmu_interrupts: Vec::new(),
+        }
+    }
+
+    pub fn dvm(mut self) -> Self {
+        self.dvm = true;
+        self
+    }
+
+    pub fn coherent_walk(mut self) -> Self {
+        self.coherent_walk = true;
+        self
+    }
+
+    pub fn add_context_interrupt(&mut self, gsiv: u32, trigger: Trigger) {
+        self.context_interrupts
+            .push(SmmuInterrupt { gsiv, trigger });
+    }
+
+    pub fn add_pmu_interrupt(&mut self, gsiv: u32, trigger: Trigger) {
+        self.pmu_interrupts.push(SmmuInterrupt { gsiv, trigger });
+    }
+}
+
+impl Node for Smmu {
+    fn node_type(&self) -> NodeType {
+        NodeType::Smmu
+    }
+
+    fn revision(&self) -> u8 {
+        3
+    }
+
+    fn data_len(&self) -> usize {
+        Self::INTERRUPT_OFFSET - Entry::HEADER_LEN
+            + 8 * (2 + self.context_interrupts.len() + self.pmu_interrupts.len())
+    }
+
+    fn write_data(&self, _offsets: &[u32], sink: &mut dyn AmlSink) {
+        let context_offset = Self::INTERRUPT_OFFSET + 16;
+        let pmu_offset = context_offset + 8 * self.context_interrupts.len();
+
+        sink.qword(self.base_address);
+        sink.qword(self.span);
+        sink.dword(self.model as u32);
+        sink.dword(self.dvm as u32 | (self.coherent_walk as u32) << 1);
+        sink.dword(Self::INTERRUPT_OFFSET as u32);
+        sink.dword(self.context_interrupts.len() as u32);
+        sink.dword(context_offset as u32);
+        sink.dword(self.pmu_interrupts.len() as u32);
+        sink.dword(pmu_offset as u32);
+
+        for interrupt in self
+            .global_interrupts
+            .iter()
+            .chain(&self.context_interrupts)
+            .chain(&self.pmu_interrupts)
+        {
+            interrupt.to_aml_bytes(sink);
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Smmuv3Model {
+    Generic = 0,
+    HiSiliconHi161x = 1,
+    CaviumCn99xx = 2,
+}
+
+/// SMMUv3, whose interrupts are either wired or signaled through MSIs
+pub struct Smmuv3 {
+    base_address: u64,
+    model: Smmuv3Model,
+    coherent: bool,
+    vatos_address: u64,
+    // Event, PRI, GERR and Sync interrupts
+    interrupts: [u32; 4],
+    proximity_domain: Option<u32>,
+    device_id_mapping: Option<u32>,
+}
+
+impl Smmuv3 {
+    pub fn new(base_address: u64) -> Self {
+        Self {
+            base_address,
+            model: Smmuv3Model::Generic,
+            coherent: false,
+            vatos_address: 0,
+            interrupts: [0; 4],
+            proximity_domain: None,
+            device_id_mapping: None,
+        }
+    }
+
+    pub fn model(mut self, model: Smmuv3Model) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Overrides the COHACC bit of the SMMU_IDR0 register
+    pub fn coherent(mut self) -> Self {
+        self.coherent = true;
+        self
+    }
+
+    pub fn vatos(mut self, address: u64) -> Self {
+        self.vatos_address = address;
+        self
+    }
+
+    /// Wired interrupts of the SMMU, which are assumed to be edge
+    /// triggered
+    pub fn interrupts(mut self, event: u32, pri: u32, gerr: u32, sync: u32) -> Self {
+        self.interrupts = [event, pri, gerr, sync];
+        self
+    }
+
+    pub fn proximity_domain(mut self, proximity_domain: u32) -> Self {
+        self.proximity_domain = Some(proximity_domain);
+        self
+    }
+
+    /// Index, in the ID mappings of the node, of the single mapping
+    /// giving the device ID of the MSIs of the SMMU
+    pub fn device_id_mapping(mut self, index: u32) -> Self {
+        self.device_id_mapping = Some(index);
+        self
+    }
+}
+
+impl Node for Smmuv3 {
+    fn node_type(&self) -> NodeType {
+        NodeType::Smmuv3
+    }
+
+    fn revision(&self) -> u8 {
+        5
+    }
+
+    fn data_len(&self) -> usize {
+        52
+    }
+
+    fn write_data(&self, _offsets: &[u32], sink: &mut dyn AmlSink) {
+        let flags = self.coherent as u32
+            | (self.proximity_domain.is_some() as u32) << 3
+            | (self.device_id_mapping.is_some() as u32) << 4;
+
+        sink.qword(self.base_address);
+        sink.dword(flags);
+        sink.dword(0); // reserved
+        sink.qword(self.vatos_address);
+        sink.dword(self.model as u32);
+        for gsiv in self.interrupts {
+            sink.dword(gsiv);
+        }
+        sink.dword(self.proximity_domain.unwrap_or(0));
+        sink.dword(self.device_id_mapping.unwrap_or(0));
+    }
+}
+
+/// Performance Monitoring Counter Group of an SMMUv3, a root complex
+/// or a named component
+pub struct Pmcg {
+    page0_address: u64,
+    page1_address: u64,
+    overflow_gsiv: u32,
+    node: NodeHandle,
+}
+
+impl Pmcg {
+    /// `node` is the node whose traffic is monitored
+    pub fn new(page0_address: u64, overflow_gsiv: u32, node: NodeHandle) -> Self {
+        Self {
+            page0_address,
+            page1_address: 0,
+            overflow_gsiv,
+            node,
+        }
+    }
+
+    pub fn page1(mut self, address: u64) -> Self {
+        self.page1_address = address;
+        self
+    }
+}
+
+impl Node for Pmcg {
+    fn node_type(&self) -> NodeType {
+        NodeType::Pmcg
+    }
+
+    fn revision(&self) -> u8 {
+        2
+    }
+
+    fn data_len(&self) -> usize {
+        24
+    }
+
+    fn write_data(&self, offsets: &[u32], sink: &mut dyn AmlSink) {
+        sink.qword(self.page0_address);
+        sink.dword(self.overflow_gsiv);
+        sink.dword(offsets[self.node.0]);
+        sink.qword(self.page1_address);
+    }
+}
+
+/// Memory attributes of the ranges of an RMR node
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RmrAttributes {
+    DeviceNGnRnE = 0,
+    DeviceNGnRE = 1,
+    DeviceNGRE = 2,
+    DeviceGRE = 3,
+    NormalNonCacheable = 4,
+    NormalWriteBack = 5,
+}
+
+/// Reserved Memory Ranges that must stay mapped for the devices whose
+/// IDs are given by the ID mappings of the node, e.g. when handing
+/// over from the firmware
+pub struct Rmr {
+    remapping_permitted: bool,
+    privileged: bool,
+    attributes: RmrAttributes,
+    ranges: Vec<(u64, u64)>,
+}
+
+impl Rmr {
+    pub fn new(attributes: RmrAttributes) -> Self {
+        Self {
+            remapping_permitted: false,
+            privileged: false,
+            attributes,
+            ranges: Vec::new(),
+        }
+    }
+
+    pub fn remapping_permitted(mut self) -> Self {
+        self.remapping_permitted = true;
+        self
+    }
+
+    pub fn privileged(mut self) -> Self {
+        self.privileged = true;
+        self
+    }
+
+    pub fn add_range(&mut self, base: u64, length: u64) {
+        self.ranges.push((base, length));
+    }
+}
+
+impl Node for Rmr {
+    fn node_type(&self) -> NodeType {
+        NodeType::Rmr
+    }
+
+    fn revision(&self) -> u8 {
+        3
+    }
+
+    fn data_len(&self) -> usize {
+        12 + 20 * self.ranges.len()
+    }
+
+    fn write_data(&self, _offsets: &[u32], sink: &mut dyn AmlSink) {
+        let flags = self.remapping_permitted as u32
+            | (self.privileged as u32) << 1
+            | (self.attributes as u32) << 2;
+
+        sink.dword(flags);
+        sink.dword(self.ranges.len() as u32);
+        // The ranges directly follow this field
+        sink.dword((Entry::HEADER_LEN + 12) as u32);
+        for (base, length) in &self.ranges {
+            sink.qword(*base);
+            sink.qword(*length);
+            sink.dword(0); // reserved
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_table;
+    use alloc::vec;
+
+    #[test]
+    fn test_iort() {
+        let iort = IORT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef);
+        let bytes = check_table(&iort);
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(bytes[0..4], *b"IORT");
+        assert_eq!(bytes[36..48], [0, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_root_complex_its() {
+        let mut iort = IORT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef);
+        let its = iort.add_its_group(ItsGroup::new(vec![0]));
+        let rc = iort.add_root_complex(RootComplex::new(0, 48).cache_coherent().ats());
+        iort.add_id_mapping(rc, IdMapping::new(0, 0x10000, 0, its));
+
+        let bytes = check_table(&iort);
+        assert_eq!(bytes.len(), 48 + 24 + 60);
+        assert_eq!(bytes[36..40], [2, 0, 0, 0]);
+
+        // ITS group
+        assert_eq!(
+            bytes[48..72],
+            [0, 24, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]
+        );
+        // Root complex, mapping all the RIDs to the ITS group at 48
+        assert_eq!(
+            bytes[72..],
+            [
+                2, 60, 0, 4, 1, 0, 0, 0, 1, 0, 0, 0, 40, 0, 0, 0, // header
+                1, 0, 0, 0, 0, 0, 0, 3, // memory access properties
+                1, 0, 0, 0, // ATS
+                0, 0, 0, 0, // segment
+                48, 0, 0, 0, 0, 0, 0, 0, // address size limit, PASID
+                0, 0, 0, 0, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 48, 0, 0, 0, 0, 0, 0, 0, // mapping
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forward_reference() {
+        let mut iort = IORT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef);
+        let rc = iort.add_root_complex(RootComplex::new(0, 48));
+        let smmu = iort.add_smmuv3(
+            Smmuv3::new(0x900_0000)
+                .interrupts(74, 75, 77, 76)
+                .proximity_domain(1),
+        );
+        let its = iort.add_its_group(ItsGroup::new(vec![0, 1]));
+        let pmcg = iort.add_pmcg(Pmcg::new(0x910_0000, 80, smmu).page1(0x912_0000));
+        iort.add_id_mapping(rc, IdMapping::new(0, 0x100, 0, smmu));
+        iort.add_id_mapping(smmu, IdMapping::new(0, 0x100, 0, its));
+        iort.add_id_mapping(pmcg, IdMapping::single(0x200, its));
+
+        let bytes = check_table(&iort);
+        let rc_offset = 48;
+        let smmu_offset = rc_offset + 40 + 20;
+        let its_offset = smmu_offset + 68 + 20;
+        let pmcg_offset = its_offset + 28;
+        assert_eq!(bytes.len(), pmcg_offset + 40 + 20);
+
+        // The root complex mapping references the SMMU added after it
+        assert_eq!(
+            bytes[rc_offset + 52..rc_offset + 56],
+            (smmu_offset as u32).to_le_bytes()
+        );
+        // SMMUv3 flags and interrupts
+        assert_eq!(bytes[smmu_offset + 24..smmu_offset + 28], [8, 0, 0, 0]);
+        assert_eq!(bytes[smmu_offset + 44], 74);
+        assert_eq!(bytes[smmu_offset + 60], 1);
+        assert_eq!(
+            bytes[smmu_offset + 80..smmu_offset + 84],
+            (its_offset as u32).to_le_bytes()
+        );
+        // PMCG node reference and single mapping
+        assert_eq!(
+            bytes[pmcg_offset + 28..pmcg_offset + 32],
+            (smmu_offset as u32).to_le_bytes()
+        );
+        assert_eq!(bytes[pmcg_offset + 56..pmcg_offset + 60], [1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_named_component() {
+        let mut iort = IORT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef);
+        let its = iort.add_its_group(ItsGroup::new(vec![0]));
+        let nc = iort.add_named_component(NamedComponent::new("\\_SB.DMA0", 40).stall());
+        iort.add_id_mapping(nc, IdMapping::new(0, 4, 0x40, its));
+
+        let bytes = check_table(&iort);
+        let nc_offset = 48 + 24;
+        // 29 bytes followed by the name and its terminator, padded to
+        // 4 bytes
+        assert_eq!(bytes[nc_offset + 1], 40 + 20);
+        assert_eq!(bytes[nc_offset + 12], 40);
+        assert_eq!(bytes[nc_offset + 28], 40);
+        assert_eq!(bytes[nc_offset + 29..nc_offset + 38], *b"\\_SB.DMA0");
+        assert_eq!(bytes[nc_offset + 38..nc_offset + 40], [0, 0]);
+        assert_eq!(bytes[nc_offset + 44..nc_offset + 48], [3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_smmu_rmr() {
+        let mut iort = IORT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef);
+        let mut smmu = Smmu::new(
+            SmmuModel::Mmu500,
+            0x2b40_0000,
+            0x10000,
+            40,
+            41,
+            Trigger::Level,
+        );
+        smmu.add_context_interrupt(42, Trigger::Edge);
+        smmu.add_context_interrupt(43, Trigger::Edge);
+        smmu.add_pmu_interrupt(44, Trigger::Level);
+        let smmu = iort.add_smmu(smmu.coherent_walk());
+
+        let mut rmr = Rmr::new(RmrAttributes::NormalWriteBack).remapping_permitted();
+        rmr.add_range(0x8000_0000, 0x10_0000);
+        let rmr = iort.add_rmr(rmr);
+        iort.add_id_mapping(rmr, IdMapping::single(0x20, smmu));
+
+        let bytes = check_table(&iort);
+        let smmu_offset = 48;
+        let rmr_offset = smmu_offset + 60 + 16 + 24;
+        assert_eq!(bytes.len(), rmr_offset + 28 + 20 + 20);
+
+        // Model, flags and the interrupt arrays
+        assert_eq!(
+            bytes[smmu_offset + 32..smmu_offset + 60],
+            [
+                3, 0, 0, 0, 2, 0, 0, 0, 60, 0, 0, 0, 2, 0, 0, 0, 76, 0, 0, 0, 1, 0, 0, 0, 92, 0, 0,
+                0
+            ]
+        );
+        assert_eq!(
+            bytes[smmu_offset + 76..smmu_offset + 84],
+            [42, 0, 0, 0, 1, 0, 0, 0]
+        );
+
+        // RMR flags, range and ID mapping
+        assert_eq!(bytes[rmr_offset + 12..rmr_offset + 16], [48, 0, 0, 0]);
+        assert_eq!(bytes[rmr_offset + 16..rmr_offset + 20], [0x15, 0, 0, 0]);
+        assert_eq!(bytes[rmr_offset + 24..rmr_offset + 28], [28, 0, 0, 0]);
+        assert_eq!(
+            bytes[rmr_offset + 28..rmr_offset + 36],
+            0x8000_0000u64.to_le_bytes()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_its_group_mapping() {
+        let mut iort = IORT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef);
+        let its = iort.add_its_group(ItsGroup::new(vec![0]));
+        iort.add_id_mapping(its, IdMapping::new(0, 1, 0, its));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not belong to this IORT")]
+    fn test_foreign_handle() {
+        let mut other = IORT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef);
+        other.add_its_group(ItsGroup::new(vec![0]));
+        let its = other.add_its_group(ItsGroup::new(vec![1]));
+
+        let mut iort = IORT::new(*b"FOOBAR", *b"DECAFCOF", 0xdead_beef);
+        let rc = iort.add_root_complex(RootComplex::new(0, 48));
+        iort.add_id_mapping(rc, IdMapping::new(0, 0x1_0000, 0, its));
+    }
+}
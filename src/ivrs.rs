@@ -0,0 +1,768 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::IntoBytes;
+
+extern crate alloc;
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{u8sum, viot::PciDevice, Aml, AmlSink, Checksum, TableHeader};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum IvInfoFlags {
+    EfrSupported = 1 << 0,
+    DmaGuardOptIn = 1 << 1,
+    HtAtsReserved = 1 << 22,
+}
+
+pub struct IVRS {
+    header: TableHeader,
+    checksum: Checksum,
+    iv_info: u32,
+    blocks: Vec<Box<dyn Aml>>,
+}
+
+impl IVRS {
+    /// `pa_size` and `va_size` are the maximum physical and virtual
+    /// address sizes supported by the IOMMUs, in bits
+    pub fn new(
+        oem_id: [u8; 6],
+        oem_table_id: [u8; 8],
+        oem_revision: u32,
+        pa_size: u8,
+        va_size: u8,
+    ) -> Self {
+        assert!(pa_size <= 64 && va_size <= 64);
+
+        let mut header = TableHeader {
+            signature: *b"IVRS",
+            length: (Self::header_len() as u32).into(),
+            revision: 2,
+            checksum: 0,
+            oem_id,
+            oem_table_id,
+            oem_revision: oem_revision.into(),
+            creator_id: crate::CREATOR_ID,
+            creator_revision: crate::CREATOR_REVISION,
+        };
+
+        // The only valid guest virtual address size is 48 bits
+        let iv_info = (2 << 5) | (pa_size as u32) << 8 | (va_size as u32) << 15;
+        let mut cksum = Checksum::default();
+        cksum.append(header.as_bytes());
+        cksum.append(iv_info.as_bytes());
+        header.checksum = cksum.value();
+
+        Self {
+            header,
+            checksum: cksum,
+            iv_info,
+            blocks: Vec::new(),
+        }
+    }
+
+    fn header_len() -> usize {
+        TableHeader::len() + 12
+    }
+
+    pub fn flag(mut self, flag: IvInfoFlags) -> Self {
+        let iv_info = self.iv_info | flag as u32;
+        self.checksum.delete(self.iv_info.as_bytes());
+        self.checksum.append(iv_info.as_bytes());
+        self.iv_info = iv_info;
+        self.header.checksum = self.checksum.value();
+        self
+    }
+
+    fn update_header(&mut self, sum: u8, len: u32) {
+        let old_len = self.header.length.get();
+        let new_len = len + old_len;
+        self.header.length.set(new_len);
+
+        // Remove the bytes from the old length, add the new length
+        // and the new data.
+        self.checksum.delete(old_len.as_bytes());
+        self.checksum.append(new_len.as_bytes());
+        self.checksum.add(sum);
+
+        self.header.checksum = self.checksum.value();
+    }
+
+    pub fn add_ivhd(&mut self, ivhd: Ivhd) {
+        self.update_header(ivhd.u8sum(), ivhd.len() as u32);
+        self.blocks.push(Box::new(ivhd));
+    }
+
+    pub fn add_ivmd(&mut self, ivmd: Ivmd) {
+        self.update_header(ivmd.u8sum(), Ivmd::len() as u32);
+        self.blocks.push(Box::new(ivmd));
+    }
+}
+
+/// The I/O Virtualization Reporting Structure describes the AMD-Vi
+/// IOMMUs of the platform, the devices they manage and the memory
+/// ranges requiring a specific treatment.
+impl Aml for IVRS {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.vec(self.header.as_bytes());
+        sink.dword(self.iv_info);
+        sink.qword(0); // reserved
+
+        for block in &self.blocks {
+            block.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
+}
+
+/// Settings of the device table entries of the devices covered by a
+/// device entry
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DteSetting {
+    InitPass = 1 << 0,
+    ExtIntPass = 1 << 1,
+    NmiPass = 1 << 2,
+    Lint0Pass = 1 << 6,
+    Lint1Pass = 1 << 7,
+}
+
+#[repr(u8)]
+enum DeviceEntryType {
+    All = 0x1,
+    Select = 0x2,
+    StartOfRange = 0x3,
+    EndOfRange = 0x4,
+    AliasSelect = 0x42,
+    AliasStartOfRange = 0x43,
+    ExtendedSelect = 0x46,
+    ExtendedStartOfRange = 0x47,
+    Special = 0x48,
+    AcpiHid = 0xf0,
+}
+
+#[derive(Copy, Clone)]
+#[repr(u8)]
+enum SpecialVariety {
+    IoApic = 1,
+    Hpet = 2,
+}
+
+/// Unique ID of the device of an ACPI HID device entry, matching its
+/// `_UID` object
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AcpiUid {
+    None,
+    Integer(u64),
+    String(String),
+}
+
+enum EntryKind {
+    All,
+    Select(u16),
+    Range(u16, u16),
+    Alias(u16, u16),
+    AliasRange(u16, u16, u16),
+    Extended(u16, u32),
+    ExtendedRange(u16, u16, u32),
+    Special(SpecialVariety, u8, u16),
+    AcpiHid {
+        device_id: u16,
+        hid: [u8; 8],
+        cid: [u8; 8],
+        uid: AcpiUid,
+    },
+}
+
+/// Devices managed by an IOMMU, along with the settings of their
+/// device table entries
+pub struct DeviceEntry {
+    kind: EntryKind,
+    segment: Option<u16>,
+    dte: u8,
+}
+
+impl DeviceEntry {
+    fn new(kind: EntryKind, segment: Option<u16>) -> Self {
+        Self {
+            kind,
+            segment,
+            dte: 0,
+        }
+    }
+
+    fn bdf_range(first: &PciDevice, last: &PciDevice) -> (u16, u16, u16) {
+        assert!(first.segment == last.segment);
+        assert!(first.as_bdf() < last.as_bdf());
+        (first.segment, first.as_bdf(), last.as_bdf())
+    }
+
+    /// All the devices of the segment of the IOMMU
+    pub fn all() -> Self {
+        Self::new(EntryKind::All, None)
+    }
+
+    pub fn select(device: &PciDevice) -> Self {
+        Self::new(EntryKind::Select(device.as_bdf()), Some(device.segment))
+    }
+
+    /// The devices from `first` to `last`, both included
+    pub fn range(first: &PciDevice, last: &PciDevice) -> Self {
+        let (segment, first, last) = Self::bdf_range(first, last);
+        Self::new(EntryKind::Range(first, last), Some(segment))
+    }
+
+    /// `device`, whose requests carry the ID of `source`
+    pub fn alias(device: &PciDevice, source: &PciDevice) -> Self {
+        Self::new(
+            EntryKind::Alias(device.as_bdf(), source.as_bdf()),
+            Some(device.segment),
+        )
+    }
+
+    /// The devices from `first` to `last`, whose requests carry the ID
+    /// of `source`
+    pub fn alias_range(first: &PciDevice, last: &PciDevice, source: &PciDevice) -> Self {
+        let (segment, first, last) = Self::bdf_range(first, last);
+        Self::new(
+            EntryKind::AliasRange(first, last, source.as_bdf()),
+            Some(segment),
+        )
+    }
+
+    /// `device`, with extended settings such as ATS being disallowed
+    pub fn extended(device: &PciDevice, extended_dte: u32) -> Self {
+        Self::new(
+            EntryKind::Extended(device.as_bdf(), extended_dte),
+            Some(device.segment),
+        )
+    }
+
+    pub fn extended_range(first: &PciDevice, last: &PciDevice, extended_dte: u32) -> Self {
+        let (segment, first, last) = Self::bdf_range(first, last);
+        Self::new(
+            EntryKind::ExtendedRange(first, last, extended_dte),
+            Some(segment),
+        )
+    }
+
+    /// I/O APIC whose ID is `ioapic_id`, as in the MADT, sending its
+    /// interrupts with the ID of `device`
+    pub fn ioapic(ioapic_id: u8, device: &PciDevice) -> Self {
+        Self::new(
+            EntryKind::Special(SpecialVariety::IoApic, ioapic_id, device.as_bdf()),
+            Some(device.segment),
+        )
+    }
+
+    /// HPET whose number is `hpet_number`, as in the HPET table,
+    /// sending its interrupts with the ID of `device`
+    pub fn hpet(hpet_number: u8, device: &PciDevice) -> Self {
+        Self::new(
+            EntryKind::Special(SpecialVariety::Hpet, hpet_number, device.as_bdf()),
+            Some(device.segment),
+        )
+    }
+
+    /// Device of the namespace identified by its `_HID`, `_CID` and
+    /// `_UID`, sending its requests with the ID of `device`. Only
+    /// allowed in IVHD blocks of type 40h.
+    pub fn acpi_hid(device: &PciDevice, hid: &str, cid: Option<&str>, uid: AcpiUid) -> Self {
+        Self::new(
+            EntryKind::AcpiHid {
+                device_id: device.as_bdf(),
+                hid: Self::id(hid),
+                cid: cid.map_or([0; 8], Self::id),
+                uid,
+            },
+            Some(device.segment),
+        )
+    }
+
+    fn id(id: &str) -> [u8; 8] {
+        assert!(id.len() <= 8);
+        let mut bytes = [0; 8];
+        bytes[..id.len()].copy_from_slice(id.as_bytes());
+        bytes
+    }
+
+    pub fn dte(mut self, setting: DteSetting) -> Self {
+        self.dte |= setting as u8;
+        self
+    }
+
+    fn uid_bytes(uid: &AcpiUid) -> &[u8] {
+        match uid {
+            AcpiUid::None => &[],
+            AcpiUid::Integer(uid) => uid.as_bytes(),
+            AcpiUid::String(uid) => uid.as_bytes(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match &self.kind {
+            EntryKind::All | EntryKind::Select(_) => 4,
+            EntryKind::Range(..) => 8,
+            EntryKind::Alias(..) | EntryKind::Extended(..) | EntryKind::Special(..) => 8,
+            EntryKind::AliasRange(..) | EntryKind::ExtendedRange(..) => 12,
+            EntryKind::AcpiHid { uid, .. } => 22 + Self::uid_bytes(uid).len(),
+        }
+    }
+
+    fn end_of_range(sink: &mut dyn AmlSink, last: u16) {
+        sink.byte(DeviceEntryType::EndOfRange as u8);
+        sink.word(last);
+        sink.byte(0);
+    }
+}
+
+impl Aml for DeviceEntry {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        match &self.kind {
+            EntryKind::All => {
+                sink.byte(DeviceEntryType::All as u8);
+                sink.word(0); // reserved
+                sink.byte(self.dte);
+            }
+            EntryKind::Select(device) => {
+                sink.byte(DeviceEntryType::Select as u8);
+                sink.word(*device);
+                sink.byte(self.dte);
+            }
+            EntryKind::Range(first, last) => {
+                sink.byte(DeviceEntryType::StartOfRange as u8);
+                sink.word(*first);
+                sink.byte(self.dte);
+                Self::end_of_range(sink, *last);
+            }
+            EntryKind::Alias(device, source) => {
+                sink.byte(DeviceEntryType::AliasSelect as u8);
+                sink.word(*device);
+                sink.byte(self.dte);
+                sink.byte(0); // reserved
+                sink.word(*source);
+                sink.byte(0); // reserved
+            }
+            EntryKind::AliasRange(first, last, source) => {
+                sink.byte(DeviceEntryType::AliasStartOfRange as u8);
+                sink.word(*first);
+                sink.byte(self.dte);
+                sink.byte(0); // reserved
+                sink.word(*source);
+                sink.byte(0); // reserved
+                Self::end_of_range(sink, *last);
+            }
+            EntryKind::Extended(device, extended_dte) => {
+                sink.byte(DeviceEntryType::ExtendedSelect as u8);
+                sink.word(*device);
+                sink.byte(self.dte);
+                sink.dword(*extended_dte);
+            }
+            EntryKind::ExtendedRange(first, last, extended_dte) => {
+                sink.byte(DeviceEntryType::ExtendedStartOfRange as u8);
+                sink.word(*first);
+                sink.byte(self.dte);
+                sink.dword(*extended_dte);
+                Self::end_of_range(sink, *last);
+            }
+            EntryKind::Special(variety, handle, device) => {
+                sink.byte(DeviceEntryType::Special as u8);
+                sink.word(0); // reserved
+                sink.byte(self.dte);
+                sink.byte(*handle);
+                sink.word(*device);
+                sink.byte(*variety as u8);
+            }
+            EntryKind::AcpiHid {
+                device_id,
+                hid,
+                cid,
+                uid,
+            } => {
+                let uid_format = match uid {
+                    AcpiUid::None => 0,
+                    AcpiUid::Integer(_) => 1,
+                    AcpiUid::String(_) => 2,
+                };
+                let uid = Self::uid_bytes(uid);
+
+                sink.byte(DeviceEntryType::AcpiHid as u8);
+                sink.word(*device_id);
+                sink.byte(self.dte);
+                sink.vec(hid);
+                sink.vec(cid);
+                sink.byte(uid_format);
+                sink.byte(uid.len() as u8);
+                sink.vec(uid);
+            }
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum IvhdType {
+    /// Fixed-length entries, with the IOMMU feature reporting field
+    Type10 = 0x10,
+    /// Fixed-length entries, with the EFR register image
+    Type11 = 0x11,
+    /// Fixed and variable-length entries, with the EFR register images
+    Type40 = 0x40,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum IvhdFlags {
+    HtTunEn = 1 << 0,
+    PassPw = 1 << 1,
+    ResPassPw = 1 << 2,
+    Isoc = 1 << 3,
+    IotlbSup = 1 << 4,
+    Coherent = 1 << 5,
+    PrefSup = 1 << 6,
+    PprSup = 1 << 7,
+}
+
+/// I/O Virtualization Hardware Definition, describing an IOMMU and the
+/// devices it manages
+pub struct Ivhd {
+    ivhd_type: IvhdType,
+    flags: u8,
+    segment: u16,
+    device_id: u16,
+    capability_offset: u16,
+    base_address: u64,
+    iommu_info: u16,
+    attributes: u32,
+    efr: [u64; 2],
+    entries: Vec<DeviceEntry>,
+}
+
+impl Ivhd {
+    /// The IOMMU is the PCI function `iommu`, whose capability block is
+    /// at `capability_offset` in its configuration space
+    pub fn new(
+        ivhd_type: IvhdType,
+        iommu: &PciDevice,
+        capability_offset: u16,
+        base_address: u64,
+    ) -> Self {
+        Self {
+            ivhd_type,
+            flags: 0,
+            segment: iommu.segment,
+            device_id: iommu.as_bdf(),
+            capability_offset,
+            base_address,
+            iommu_info: 0,
+            attributes: 0,
+            efr: [0; 2],
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn flag(mut self, flag: IvhdFlags) -> Self {
+        self.flags |= flag as u8;
+        self
+    }
+
+    /// Message number of the MSI and the HyperTransport unit ID of the
+    /// IOMMU
+    pub fn info(mut self, msi_number: u8, unit_id: u8) -> Self {
+        assert!(msi_number < 32);
+        assert!(unit_id < 32);
+        self.iommu_info = msi_number as u16 | (unit_id as u16) << 8;
+        self
+    }
+
+    /// IOMMU feature reporting field of type 10h blocks, or IOMMU
+    /// attributes of the other types
+    pub fn attributes(mut self, attributes: u32) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    /// Image of the extended feature registers, only for the types 11h
+    /// and 40h. The second register is reserved for type 11h.
+    pub fn efr(mut self, efr: u64, efr2: u64) -> Self {
+        assert!(self.ivhd_type != IvhdType::Type10);
+        self.efr = [efr, efr2];
+        self
+    }
+
+    pub fn add_entry(&mut self, entry: DeviceEntry) {
+        if let EntryKind::AcpiHid { .. } = entry.kind {
+            assert!(self.ivhd_type == IvhdType::Type40);
+        }
+        if let Some(segment) = entry.segment {
+            assert!(segment == self.segment);
+        }
+        self.entries.push(entry);
+    }
+
+    fn header_len(&self) -> usize {
+        match self.ivhd_type {
+            IvhdType::Type10 => 24,
+            IvhdType::Type11 | IvhdType::Type40 => 40,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.header_len() + self.entries.iter().map(|e| e.len()).sum::<usize>()
+    }
+
+    fn u8sum(&self) -> u8 {
+        u8sum(self)
+    }
+}
+
+impl Aml for Ivhd {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.byte(self.ivhd_type as u8);
+        sink.byte(self.flags);
+        sink.word(self.len() as u16);
+        sink.word(self.device_id);
+        sink.word(self.capability_offset);
+        sink.qword(self.base_address);
+        sink.word(self.segment);
+        sink.word(self.iommu_info);
+        sink.dword(self.attributes);
+        if self.ivhd_type != IvhdType::Type10 {
+            sink.qword(self.efr[0]);
+            sink.qword(self.efr[1]);
+        }
+
+        for entry in &self.entries {
+            entry.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum IvmdFlags {
+    Unity = 1 << 0,
+    Read = 1 << 1,
+    Write = 1 << 2,
+    ExclusionRange = 1 << 3,
+}
+
+#[derive(Copy, Clone)]
+#[repr(u8)]
+enum IvmdType {
+    All = 0x20,
+    Select = 0x21,
+    Range = 0x22,
+}
+
+/// I/O Virtualization Memory Definition, describing a memory range
+/// requiring a specific treatment for some devices
+pub struct Ivmd {
+    ivmd_type: IvmdType,
+    flags: u8,
+    device_id: u16,
+    aux_data: u16,
+    start_address: u64,
+    length: u64,
+}
+
+impl Ivmd {
+    fn new(ivmd_type: IvmdType, device_id: u16, aux_data: u16, start: u64, length: u64) -> Self {
+        Self {
+            ivmd_type,
+            flags: 0,
+            device_id,
+            aux_data,
+            start_address: start,
+            length,
+        }
+    }
+
+    /// The range applies to all the devices
+    pub fn all(start: u64, length: u64) -> Self {
+        Self::new(IvmdType::All, 0, 0, start, length)
+    }
+
+    pub fn select(device: &PciDevice, start: u64, length: u64) -> Self {
+        Self::new(IvmdType::Select, device.as_bdf(), 0, start, length)
+    }
+
+    /// The range applies to the devices from `first` to `last`, both
+    /// included
+    pub fn range(first: &PciDevice, last: &PciDevice, start: u64, length: u64) -> Self {
+        let (_, first, last) = DeviceEntry::bdf_range(first, last);
+        Self::new(IvmdType::Range, first, last, start, length)
+    }
+
+    pub fn flag(mut self, flag: IvmdFlags) -> Self {
+        self.flags |= flag as u8;
+        self
+    }
+
+    fn len() -> usize {
+        32
+    }
+
+    fn u8sum(&self) -> u8 {
+        u8sum(self)
+    }
+}
+
+impl Aml for Ivmd {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.byte(self.ivmd_type as u8);
+        sink.byte(self.flags);
+        sink.word(Self::len() as u16);
+        sink.word(self.device_id);
+        sink.word(self.aux_data);
+        sink.qword(0); // reserved
+        sink.qword(self.start_address);
+        sink.qword(self.length);
+    }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_table;
+    use alloc::vec;
+
+    fn entry_bytes(entry: &DeviceEntry) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        entry.to_aml_bytes(&mut bytes);
+        assert_eq!(entry.aml_len(), bytes.len());
+        bytes
+    }
+
+    #[test]
+    fn test_ivrs() {
+        let ivrs = IVRS::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef, 48, 64)
+            .flag(IvInfoFlags::EfrSupported);
+        let bytes = check_table(&ivrs);
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(bytes[0..4], *b"IVRS");
+        // GVAsize 48 bits, PAsize 48 and VAsize 64
+        assert_eq!(bytes[36..40], [0x41, 0x30, 0x20, 0x00]);
+    }
+
+    #[test]
+    fn test_device_entries() {
+        let first = PciDevice::new(0, 0, 1, 0);
+        let last = PciDevice::new(0, 0, 0x1f, 7);
+        let bridge = PciDevice::new(0, 0, 0x14, 4);
+        let southbridge = PciDevice::new(0, 0, 0x14, 0);
+
+        assert_eq!(entry_bytes(&DeviceEntry::all()), [1, 0, 0, 0]);
+        assert_eq!(
+            entry_bytes(&DeviceEntry::select(&first).dte(DteSetting::InitPass)),
+            [2, 0x08, 0, 1]
+        );
+        assert_eq!(
+            entry_bytes(&DeviceEntry::range(&first, &last)),
+            [3, 0x08, 0, 0, 4, 0xff, 0, 0]
+        );
+        assert_eq!(
+            entry_bytes(&DeviceEntry::alias_range(
+                &PciDevice::new(0, 1, 0, 0),
+                &PciDevice::new(0, 1, 0x1f, 7),
+                &bridge
+            )),
+            [0x43, 0x00, 0x01, 0, 0, 0xa4, 0, 0, 4, 0xff, 0x01, 0]
+        );
+        assert_eq!(
+            entry_bytes(
+                &DeviceEntry::ioapic(0x21, &southbridge)
+                    .dte(DteSetting::ExtIntPass)
+                    .dte(DteSetting::NmiPass)
+                    .dte(DteSetting::Lint0Pass)
+                    .dte(DteSetting::Lint1Pass)
+            ),
+            [0x48, 0, 0, 0xc6, 0x21, 0xa0, 0, 1]
+        );
+        assert_eq!(
+            entry_bytes(&DeviceEntry::hpet(0, &southbridge)),
+            [0x48, 0, 0, 0, 0, 0xa0, 0, 2]
+        );
+        assert_eq!(
+            entry_bytes(&DeviceEntry::acpi_hid(
+                &PciDevice::new(0, 0, 0x14, 5),
+                "AMDI0020",
+                None,
+                AcpiUid::String("ID00".into())
+            )),
+            vec![
+                0xf0, 0xa5, 0, 0, b'A', b'M', b'D', b'I', b'0', b'0', b'2', b'0', 0, 0, 0, 0, 0, 0,
+                0, 0, 2, 4, b'I', b'D', b'0', b'0'
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ivhd_ivmd() {
+        let mut ivrs = IVRS::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef, 48, 64);
+
+        let iommu = PciDevice::new(0, 0, 0, 2);
+        let mut ivhd = Ivhd::new(IvhdType::Type40, &iommu, 0x40, 0xfeb8_0000)
+            .flag(IvhdFlags::Coherent)
+            .info(0, 0)
+            .efr(0x0000_0246_577e_fa2d, 0);
+        ivhd.add_entry(DeviceEntry::range(
+            &PciDevice::new(0, 0, 1, 0),
+            &PciDevice::new(0, 0xff, 0x1f, 7),
+        ));
+        ivhd.add_entry(DeviceEntry::ioapic(0x21, &PciDevice::new(0, 0, 0x14, 0)));
+        ivrs.add_ivhd(ivhd);
+        check_table(&ivrs);
+
+        ivrs.add_ivmd(
+            Ivmd::select(&PciDevice::new(0, 0, 1, 0), 0x8000_0000, 0x1000)
+                .flag(IvmdFlags::Unity)
+                .flag(IvmdFlags::Read)
+                .flag(IvmdFlags::Write),
+        );
+        let bytes = check_table(&ivrs);
+        let ivmd_offset = 48 + 40 + 16;
+        assert_eq!(bytes.len(), ivmd_offset + 32);
+
+        assert_eq!(
+            bytes[48..60],
+            [0x40, 0x20, 56, 0, 0x02, 0, 0x40, 0, 0x00, 0x00, 0xb8, 0xfe]
+        );
+        assert_eq!(bytes[72..80], 0x0000_0246_577e_fa2du64.to_le_bytes());
+        assert_eq!(
+            bytes[ivmd_offset..ivmd_offset + 8],
+            [0x21, 7, 32, 0, 0x08, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_acpi_hid_fixed_length() {
+        let iommu = PciDevice::new(0, 0, 0, 2);
+        let mut ivhd = Ivhd::new(IvhdType::Type11, &iommu, 0x40, 0xfeb8_0000);
+        ivhd.add_entry(DeviceEntry::acpi_hid(
+            &PciDevice::new(0, 0, 0x14, 5),
+            "AMDI0020",
+            None,
+            AcpiUid::None,
+        ));
+    }
+}
@@ -12,24 +12,45 @@
 pub mod aml;
 pub mod bert;
 pub mod cedt;
+pub mod cpuhp;
+pub mod cpupm;
+pub mod dbg2;
+pub mod dmar;
+pub mod dsd;
 pub mod facs;
 pub mod fadt;
 pub mod gas;
+pub mod ged;
+pub mod gtdt;
 pub mod hest;
 pub mod hmat;
+pub mod hpet;
+pub mod iort;
+pub mod ivrs;
 pub mod madt;
 pub mod mcfg;
+pub mod memhp;
+pub mod nfit;
+pub mod nvdimm;
+pub mod osc;
+pub mod pcct;
+pub mod pci;
+pub mod pcihp;
+pub mod power;
 pub mod pptt;
+pub mod prt;
 pub mod rhct;
 pub mod rimt;
 pub mod rqsc;
 pub mod rsdp;
 pub mod sdt;
+pub mod sleep;
 pub mod slit;
 pub mod spcr;
 pub mod srat;
 pub mod tpm2;
 pub mod viot;
+pub mod windows;
 pub mod xsdt;
 
 extern crate alloc;
@@ -70,6 +91,157 @@ pub trait AmlSink {
     }
 }
 
+/// Error reported by a `TryAmlSink` that cannot accept more bytecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmlSinkError {
+    /// The sink does not have enough room left for the data.
+    Overflow,
+}
+
+/// Fallible variant of `AmlSink`, for sinks with a limited capacity
+/// (e.g. a reserved region of guest memory). Objects are serialized
+/// into it with `Aml::try_to_aml_bytes`, which stops at the first
+/// error.
+pub trait TryAmlSink {
+    fn try_byte(&mut self, byte: u8) -> Result<(), AmlSinkError>;
+
+    fn try_word(&mut self, word: u16) -> Result<(), AmlSinkError> {
+        self.try_vec(&word.to_le_bytes())
+    }
+
+    fn try_dword(&mut self, dword: u32) -> Result<(), AmlSinkError> {
+        self.try_vec(&dword.to_le_bytes())
+    }
+
+    fn try_qword(&mut self, qword: u64) -> Result<(), AmlSinkError> {
+        self.try_vec(&qword.to_le_bytes())
+    }
+
+    fn try_vec(&mut self, v: &[u8]) -> Result<(), AmlSinkError> {
+        for byte in v {
+            self.try_byte(*byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Adapts a `TryAmlSink` to the `AmlSink` interface. The first error
+/// is latched and everything written after it is dropped.
+struct FallibleSink<'a> {
+    sink: &'a mut dyn TryAmlSink,
+    result: Result<(), AmlSinkError>,
+}
+
+impl AmlSink for FallibleSink<'_> {
+    fn byte(&mut self, byte: u8) {
+        if self.result.is_ok() {
+            self.result = self.sink.try_byte(byte);
+        }
+    }
+
+    fn word(&mut self, word: u16) {
+        if self.result.is_ok() {
+            self.result = self.sink.try_word(word);
+        }
+    }
+
+    fn dword(&mut self, dword: u32) {
+        if self.result.is_ok() {
+            self.result = self.sink.try_dword(dword);
+        }
+    }
+
+    fn qword(&mut self, qword: u64) {
+        if self.result.is_ok() {
+            self.result = self.sink.try_qword(qword);
+        }
+    }
+
+    fn vec(&mut self, v: &[u8]) {
+        if self.result.is_ok() {
+            self.result = self.sink.try_vec(v);
+        }
+    }
+}
+
+/// An allocation-free sink writing the bytecode in place into a
+/// fixed buffer.
+///
+/// Running out of space never panics. When used as an `AmlSink` the
+/// overflow is recorded and reported by `finish()`, and `len()` keeps
+/// counting so that it reports the size the buffer would have needed.
+/// When used as a `TryAmlSink` the overflowing write returns
+/// `AmlSinkError::Overflow`.
+pub struct SliceSink<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceSink<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Number of bytes written so far, or that would have been
+    /// written if the buffer had been large enough.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn overflowed(&self) -> bool {
+        self.len > self.buf.len()
+    }
+
+    /// Return the number of bytes written to the buffer, or an error
+    /// if any of the data did not fit.
+    pub fn finish(self) -> Result<usize, AmlSinkError> {
+        if self.overflowed() {
+            Err(AmlSinkError::Overflow)
+        } else {
+            Ok(self.len)
+        }
+    }
+}
+
+impl AmlSink for SliceSink<'_> {
+    fn byte(&mut self, byte: u8) {
+        self.vec(&[byte]);
+    }
+
+    fn vec(&mut self, v: &[u8]) {
+        // Ignore the error, it is reported by finish()
+        let _ = self.try_vec(v);
+    }
+}
+
+impl TryAmlSink for SliceSink<'_> {
+    fn try_byte(&mut self, byte: u8) -> Result<(), AmlSinkError> {
+        self.try_vec(&[byte])
+    }
+
+    fn try_vec(&mut self, v: &[u8]) -> Result<(), AmlSinkError> {
+        let start = self.len;
+        self.len += v.len();
+
+        // Once the buffer has overflowed, nothing else is written.
+        match self.buf.get_mut(start..self.len) {
+            Some(dst) => {
+                dst.copy_from_slice(v);
+                Ok(())
+            }
+            None => Err(AmlSinkError::Overflow),
+        }
+    }
+}
+
 /// The trait Aml can be implemented by ACPI objects or ACPI tables to
 /// translate itself into the AML raw data.
 pub trait Aml {
@@ -77,6 +249,57 @@ pub trait Aml {
     /// AmlSink object.
     /// * `sink` - The sink used to receive the AML bytecode.
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink);
+
+    /// Serialize an ACPI object into a sink that may fail, returning
+    /// the first error it reports.
+    /// * `sink` - The sink used to receive the AML bytecode.
+    fn try_to_aml_bytes(&self, sink: &mut dyn TryAmlSink) -> Result<(), AmlSinkError> {
+        let mut sink = FallibleSink {
+            sink,
+            result: Ok(()),
+        };
+        self.to_aml_bytes(&mut sink);
+        sink.result
+    }
+
+    /// Return the number of bytes `to_aml_bytes` would produce,
+    /// without serializing the object anywhere. Objects that
+    /// contain others (e.g. `Device`, `Method`, `Package`) compute
+    /// this in constant time so that PkgLength encodings can be
+    /// emitted before their contents.
+    fn aml_len(&self) -> usize {
+        let mut counter = ByteCounter::default();
+        self.to_aml_bytes(&mut counter);
+        counter.len
+    }
+}
+
+/// A sink that discards the bytecode and only keeps track of its size.
+#[derive(Debug, Default)]
+struct ByteCounter {
+    len: usize,
+}
+
+impl AmlSink for ByteCounter {
+    fn byte(&mut self, _byte: u8) {
+        self.len += 1;
+    }
+
+    fn word(&mut self, _word: u16) {
+        self.len += 2;
+    }
+
+    fn dword(&mut self, _dword: u32) {
+        self.len += 4;
+    }
+
+    fn qword(&mut self, _qword: u64) {
+        self.len += 8;
+    }
+
+    fn vec(&mut self, v: &[u8]) {
+        self.len += v.len();
+    }
 }
 
 /// Simplify the library by treating Vec<u8> as a valid AmlSink.
@@ -190,6 +413,10 @@ macro_rules! aml_as_bytes {
             fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
                 sink.vec(self.as_bytes());
             }
+
+            fn aml_len(&self) -> usize {
+                core::mem::size_of::<Self>()
+            }
         }
     };
 }
@@ -204,6 +431,22 @@ macro_rules! mutable_setter {
     };
 }
 
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use super::Aml;
+    use alloc::vec::Vec;
+
+    /// Serialize a table, checking its length and that it sums to zero.
+    pub(crate) fn check_table(table: &dyn Aml) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        table.to_aml_bytes(&mut bytes);
+        assert_eq!(table.aml_len(), bytes.len());
+        let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
+        assert_eq!(sum, 0);
+        bytes
+    }
+}
+
 #[cfg(test)]
 mod lib_tests {
     use super::*;
@@ -226,4 +469,50 @@ mod lib_tests {
         c.add(1);
         assert!(c.value() == 254);
     }
+
+    #[test]
+    fn test_slice_sink() {
+        let mut buf = [0u8; 8];
+        let mut sink = SliceSink::new(&mut buf);
+        sink.byte(0x12);
+        sink.word(0x3456);
+        sink.dword(0x789a_bcde);
+        assert_eq!(sink.len(), 7);
+        assert!(!sink.overflowed());
+        assert_eq!(sink.finish(), Ok(7));
+        assert_eq!(buf, [0x12, 0x56, 0x34, 0xde, 0xbc, 0x9a, 0x78, 0]);
+
+        let mut buf = [0u8; 8];
+        let mut sink = SliceSink::new(&mut buf);
+        sink.dword(0x1111_1111);
+        sink.qword(0x2222_2222_2222_2222);
+        // A later write that would fit must not be written either
+        sink.byte(0x33);
+        assert!(sink.overflowed());
+        assert_eq!(sink.len(), 13);
+        assert_eq!(sink.finish(), Err(AmlSinkError::Overflow));
+        assert_eq!(buf, [0x11, 0x11, 0x11, 0x11, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_try_to_aml_bytes() {
+        let name = aml::Name::new("_UID".into(), &0x1234_5678u32);
+        let len = name.aml_len();
+
+        let mut buf = [0u8; 16];
+        let mut sink = SliceSink::new(&mut buf[..len]);
+        assert_eq!(name.try_to_aml_bytes(&mut sink), Ok(()));
+        assert_eq!(sink.finish(), Ok(len));
+        let mut bytes = alloc::vec::Vec::new();
+        name.to_aml_bytes(&mut bytes);
+        assert_eq!(&buf[..len], bytes.as_slice());
+
+        let mut buf = [0u8; 16];
+        let mut sink = SliceSink::new(&mut buf[..len - 1]);
+        assert_eq!(
+            name.try_to_aml_bytes(&mut sink),
+            Err(AmlSinkError::Overflow)
+        );
+        assert!(sink.overflowed());
+    }
 }
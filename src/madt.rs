@@ -208,6 +208,10 @@ impl Aml for MADT {
             st.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.table_header.length.get() as usize
+    }
 }
 
 /// Processor-Local APIC
@@ -239,6 +243,10 @@ impl ProcessorLocalApic {
             flags: (enabled as u32).into(),
         }
     }
+
+    pub(crate) fn processor_uid(&self) -> u32 {
+        self.processor_uid.into()
+    }
 }
 
 aml_as_bytes!(ProcessorLocalApic);
@@ -380,6 +388,10 @@ impl Gicc {
         core::mem::size_of::<Self>()
     }
 
+    pub(crate) fn processor_uid(&self) -> u32 {
+        self.acpi_processor_uid.get()
+    }
+
     pub fn new(status: EnabledStatus) -> Self {
         let flags = match status {
             EnabledStatus::Enabled => GiccFlags::Enabled as u32,
@@ -618,6 +630,10 @@ impl RINTC {
     pub fn len() -> usize {
         core::mem::size_of::<Self>()
     }
+
+    pub(crate) fn processor_uid(&self) -> u32 {
+        self.acpi_processor_uid.get()
+    }
 }
 
 assert_same_size!(RINTC, [u8; 0x24]);
@@ -793,6 +809,7 @@ mod tests {
     fn check_checksum(madt: &MADT) {
         let mut bytes = Vec::new();
         madt.to_aml_bytes(&mut bytes);
+        assert_eq!(madt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -800,6 +817,7 @@ mod tests {
     fn get_size(madt: &MADT) -> usize {
         let mut bytes = Vec::new();
         madt.to_aml_bytes(&mut bytes);
+        assert_eq!(madt.aml_len(), bytes.len());
         bytes.len()
     }
 
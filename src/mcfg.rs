@@ -85,6 +85,10 @@ impl Aml for MCFG {
             entry.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[repr(C, packed)]
@@ -116,6 +120,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         mcfg.to_aml_bytes(&mut bytes);
+        assert_eq!(mcfg.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(TableHeader::len() + 8, bytes.len());
@@ -123,6 +128,7 @@ mod tests {
         mcfg.add_ecam(0xc000_0000, 42, 0, 0x20);
         let mut bytes = Vec::new();
         mcfg.to_aml_bytes(&mut bytes);
+        assert_eq!(mcfg.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(TableHeader::len() + 8 + EcamEntry::len(), bytes.len());
@@ -130,6 +136,7 @@ mod tests {
         mcfg.add_ecam(0x1234_5678, 3920, 5, 0xfe);
         let mut bytes = Vec::new();
         mcfg.to_aml_bytes(&mut bytes);
+        assert_eq!(mcfg.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
 
@@ -0,0 +1,557 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{format, vec, vec::Vec};
+
+use crate::{
+    aml::{
+        register_field, status_field, Acquire, Add, AddressSpace, AddressSpaceCacheable, Arg,
+        CreateQWordField, Device, EISAName, Equal, GreaterEqual, If, LessEqual, LessThan, Local,
+        LogicalAnd, Method, MethodCall, Mutex, Name, Notify, OpRegion, OpRegionSpace, Or, Path,
+        Release, ResourceTemplate, Return, ShiftLeft, Store, Subtract, While, ONE, ZERO,
+    },
+    srat::MemoryAffinity,
+    Aml, AmlSink,
+};
+
+/// Layout of the register block through which the VMM reports the
+/// state of the memory slots. The registers other than the selector
+/// refer to the slot whose `_UID` was last written to the selector.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MemoryHotplugRegisters {
+    /// Base address of the block in system memory
+    pub address: u64,
+    /// Size of the block
+    pub length: u32,
+    /// Offset of the 32-bit slot selector
+    pub selector: u32,
+    /// Offset of the 8-bit status register. Bit 0 is set when the slot
+    /// is populated, bits 1 and 2 when an insertion or a removal is
+    /// pending (write 1 to acknowledge), and writing 1 to bit 3 ejects
+    /// the memory.
+    pub status: u32,
+    /// Offset of the 64-bit base address of the memory in the slot, as
+    /// two 32-bit registers (low half first)
+    pub base: u32,
+    /// Offset of the 64-bit size of the memory in the slot, as two
+    /// 32-bit registers (low half first)
+    pub size: u32,
+    /// Offset of the 32-bit register receiving the `_OST` source event
+    pub ost_event: u32,
+    /// Offset of the 32-bit register receiving the `_OST` status code
+    pub ost_status: u32,
+}
+
+impl MemoryHotplugRegisters {
+    /// Default layout, with the selector, status, base, size, `_OST`
+    /// event and `_OST` status registers at offsets 0, 4, 8, 16, 24
+    /// and 28.
+    pub fn new(address: u64) -> Self {
+        MemoryHotplugRegisters {
+            address,
+            length: 32,
+            selector: 0,
+            status: 4,
+            base: 8,
+            size: 16,
+            ost_event: 24,
+            ost_status: 28,
+        }
+    }
+}
+
+// Range of guest physical addresses where memory can be hotplugged
+struct HotplugRegion {
+    proximity_domain: u32,
+    base: u64,
+    length: u64,
+}
+
+fn slot_name(uid: u32) -> Path {
+    Path::new(&format!("M{uid:03X}"))
+}
+
+// Memory device of a single slot, forwarding to the methods of the
+// controller
+struct MemorySlot {
+    uid: u32,
+}
+
+impl Aml for MemorySlot {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let uid = self.uid;
+        let sta = MethodCall::new("MSTA".into(), vec![&uid]);
+        let crs = MethodCall::new("MCRS".into(), vec![&uid]);
+        let pxm = MethodCall::new("MPXM".into(), vec![&uid]);
+        let ej0 = MethodCall::new("MEJ0".into(), vec![&uid]);
+        let ost = MethodCall::new("MOST".into(), vec![&uid, &Arg(0), &Arg(1), &Arg(2)]);
+        Device::new(
+            slot_name(uid),
+            vec![
+                &Name::new("_HID".into(), &EISAName::new("PNP0C80")),
+                &Name::new("_UID".into(), &uid),
+                &Method::new("_STA".into(), 0, false, vec![&Return::new(&sta)]),
+                &Method::new("_CRS".into(), 0, false, vec![&Return::new(&crs)]),
+                &Method::new("_PXM".into(), 0, false, vec![&Return::new(&pxm)]),
+                &Method::new("_EJ0".into(), 1, false, vec![&ej0]),
+                &Method::new("_OST".into(), 3, false, vec![&ost]),
+            ],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+// Notify the slot selected by Arg0 with the value in Arg1
+struct SlotNotify {
+    uid: u32,
+}
+
+impl Aml for SlotNotify {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let slot = slot_name(self.uid);
+        If::new(
+            &Equal::new(&Arg(0), &self.uid),
+            vec![&Notify::new(&slot, &Arg(1))],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+// Return the proximity domain of the region containing the base
+// address in Local0
+impl Aml for HotplugRegion {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        // Inclusive, as the region may end at the top of the address
+        // space
+        let end = self.base + (self.length - 1);
+        If::new(
+            &LogicalAnd::new(
+                &GreaterEqual::new(&Local(0), &self.base),
+                &LessEqual::new(&Local(0), &end),
+            ),
+            vec![&Return::new(&self.proximity_domain)],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+// Methods of the controller accessing the registers of the slots
+struct ControllerMethods<'a> {
+    slots: u32,
+    regions: &'a [HotplugRegion],
+}
+
+impl Aml for ControllerMethods<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let lock = Path::new("MLCK");
+        let selector = Path::new("MSEL");
+        let enabled = Path::new("MEN_");
+        let inserting = Path::new("MINS");
+        let removing = Path::new("MRMV");
+        let eject = Path::new("MEJF");
+        let base_low = Path::new("MBLO");
+        let base_high = Path::new("MBHI");
+        let size_low = Path::new("MSLO");
+        let size_high = Path::new("MSHI");
+        let ost_event = Path::new("MOEV");
+        let ost_status = Path::new("MOSC");
+
+        let acquire = Acquire::new(lock.clone(), 0xffff);
+        let release = Release::new(lock);
+        let select = Store::new(&selector, &Arg(0));
+        // Local0 = (MBHI << 32) | MBLO
+        let read_base_high = ShiftLeft::new(&Local(0), &base_high, &32u8);
+        let read_base = Or::new(&Local(0), &Local(0), &base_low);
+
+        // MSTA(uid): _STA of the slot
+        Method::new(
+            "MSTA".into(),
+            1,
+            true,
+            vec![
+                &acquire,
+                &select,
+                &Store::new(&Local(0), &ZERO),
+                &If::new(
+                    &Equal::new(&enabled, &ONE),
+                    vec![&Store::new(&Local(0), &0xfu8)],
+                ),
+                &release,
+                &Return::new(&Local(0)),
+            ],
+        )
+        .to_aml_bytes(sink);
+
+        // MCRS(uid): _CRS of the slot, built from its base and size
+        let min = Path::new("MMIN");
+        let max = Path::new("MMAX");
+        let len = Path::new("MLEN");
+        let resources = Path::new("MR64");
+        Method::new(
+            "MCRS".into(),
+            1,
+            true,
+            vec![
+                &Name::new(
+                    resources.clone(),
+                    &ResourceTemplate::new(vec![&AddressSpace::new_memory(
+                        AddressSpaceCacheable::Cacheable,
+                        true,
+                        0u64,
+                        0xffff_ffff_ffff_fffeu64,
+                        None,
+                    )]),
+                ),
+                // Offsets of _MIN, _MAX and _LEN in the QWord descriptor
+                &CreateQWordField::new(&min, &resources, &14u8),
+                &CreateQWordField::new(&max, &resources, &22u8),
+                &CreateQWordField::new(&len, &resources, &38u8),
+                &acquire,
+                &select,
+                &read_base_high,
+                &read_base,
+                &Store::new(&min, &Local(0)),
+                &ShiftLeft::new(&Local(0), &size_high, &32u8),
+                &Or::new(&len, &Local(0), &size_low),
+                &Add::new(&Local(0), &min, &len),
+                &Subtract::new(&max, &Local(0), &ONE),
+                &release,
+                &Return::new(&resources),
+            ],
+        )
+        .to_aml_bytes(sink);
+
+        // MPXM(uid): _PXM of the slot, from the region its memory is in
+        let outside = Return::new(&ZERO);
+        let mut pxm_body: Vec<&dyn Aml> =
+            vec![&acquire, &select, &read_base_high, &read_base, &release];
+        pxm_body.extend(self.regions.iter().map(|r| r as &dyn Aml));
+        pxm_body.push(&outside);
+        Method::new("MPXM".into(), 1, true, pxm_body).to_aml_bytes(sink);
+
+        // MEJ0(uid): eject the memory
+        Method::new(
+            "MEJ0".into(),
+            1,
+            true,
+            vec![&acquire, &select, &Store::new(&eject, &ONE), &release],
+        )
+        .to_aml_bytes(sink);
+
+        // MOST(uid, event, status, info): _OST of the slot
+        Method::new(
+            "MOST".into(),
+            4,
+            true,
+            vec![
+                &acquire,
+                &select,
+                &Store::new(&ost_event, &Arg(1)),
+                &Store::new(&ost_status, &Arg(2)),
+                &release,
+            ],
+        )
+        .to_aml_bytes(sink);
+
+        // MTFY(uid, value): notify the slot
+        let notifies: Vec<SlotNotify> = (0..self.slots).map(|uid| SlotNotify { uid }).collect();
+        Method::new(
+            "MTFY".into(),
+            2,
+            false,
+            notifies.iter().map(|n| n as &dyn Aml).collect(),
+        )
+        .to_aml_bytes(sink);
+
+        // MSCN(): notify the OS of the pending insertions and removals
+        let notify_insert = MethodCall::new("MTFY".into(), vec![&Local(0), &ONE]);
+        let notify_remove = MethodCall::new("MTFY".into(), vec![&Local(0), &3u8]);
+        Method::new(
+            "MSCN".into(),
+            0,
+            true,
+            vec![
+                &acquire,
+                &Store::new(&Local(0), &ZERO),
+                &While::new(
+                    &LessThan::new(&Local(0), &self.slots),
+                    vec![
+                        &Store::new(&selector, &Local(0)),
+                        &If::new(
+                            &Equal::new(&inserting, &ONE),
+                            vec![&notify_insert, &Store::new(&inserting, &ONE)],
+                        ),
+                        &If::new(
+                            &Equal::new(&removing, &ONE),
+                            vec![&notify_remove, &Store::new(&removing, &ONE)],
+                        ),
+                        &Add::new(&Local(0), &Local(0), &ONE),
+                    ],
+                ),
+                &release,
+            ],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Memory hotplug controller, holding a memory device (PNP0C80) per
+/// slot. The `MSCN` method of the controller notifies the OS of the
+/// pending insertions and removals, and is meant to be called when
+/// the VMM signals a memory hotplug event, e.g. through a GED.
+pub struct MemoryHotplug {
+    path: Path,
+    registers: MemoryHotplugRegisters,
+    slots: u32,
+    regions: Vec<HotplugRegion>,
+}
+
+impl MemoryHotplug {
+    /// Create a controller for `slots` memory slots. The n-th slot has
+    /// `_UID` n, which is the value written to the selector to access
+    /// its registers.
+    pub fn new(path: Path, registers: MemoryHotplugRegisters, slots: u32) -> Self {
+        assert!(slots <= 0x1000);
+        MemoryHotplug {
+            path,
+            registers,
+            slots,
+            regions: Vec::new(),
+        }
+    }
+
+    /// Add a range of addresses where memory can be hotplugged. The
+    /// `_PXM` of a slot whose memory starts in the range returns
+    /// `proximity_domain`, while it returns 0 for memory outside of
+    /// any range.
+    pub fn add_region(&mut self, proximity_domain: u32, base: u64, length: u64) {
+        assert!(length > 0);
+        assert!(base.checked_add(length - 1).is_some());
+        self.regions.push(HotplugRegion {
+            proximity_domain,
+            base,
+            length,
+        });
+    }
+
+    /// SRAT memory affinity structures describing the hotpluggable
+    /// ranges.
+    pub fn memory_affinities(&self) -> Vec<MemoryAffinity> {
+        self.regions
+            .iter()
+            .map(|region| {
+                MemoryAffinity::new(region.proximity_domain, region.base, region.length)
+                    .enabled()
+                    .hotpluggable()
+            })
+            .collect()
+    }
+}
+
+impl Aml for MemoryHotplug {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let hid = Name::new("_HID".into(), &EISAName::new("PNP0A06"));
+        let mutex = Mutex::new("MLCK".into(), 0);
+        let region = OpRegion::new(
+            "MHPR".into(),
+            OpRegionSpace::SystemMemory,
+            &self.registers.address,
+            &self.registers.length,
+        );
+        let selector_field = register_field("MHPR", *b"MSEL", self.registers.selector);
+        let status_field = status_field(
+            "MHPR",
+            self.registers.status,
+            &[*b"MEN_", *b"MINS", *b"MRMV", *b"MEJF"],
+        );
+        let base_low_field = register_field("MHPR", *b"MBLO", self.registers.base);
+        let base_high_field = register_field("MHPR", *b"MBHI", self.registers.base + 4);
+        let size_low_field = register_field("MHPR", *b"MSLO", self.registers.size);
+        let size_high_field = register_field("MHPR", *b"MSHI", self.registers.size + 4);
+        let ost_event_field = register_field("MHPR", *b"MOEV", self.registers.ost_event);
+        let ost_status_field = register_field("MHPR", *b"MOSC", self.registers.ost_status);
+        let methods = ControllerMethods {
+            slots: self.slots,
+            regions: &self.regions,
+        };
+        let slots: Vec<MemorySlot> = (0..self.slots).map(|uid| MemorySlot { uid }).collect();
+
+        let mut children: Vec<&dyn Aml> = vec![
+            &hid,
+            &mutex,
+            &region,
+            &selector_field,
+            &status_field,
+            &base_low_field,
+            &base_high_field,
+            &size_low_field,
+            &size_high_field,
+            &ost_event_field,
+            &ost_status_field,
+            &methods,
+        ];
+        children.extend(slots.iter().map(|slot| slot as &dyn Aml));
+
+        Device::new(self.path.clone(), children).to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::srat::SRAT;
+
+    #[test]
+    fn test_slot() {
+        let slot = MemorySlot { uid: 0x1a };
+        let mut bytes = Vec::new();
+        slot.to_aml_bytes(&mut bytes);
+        assert_eq!(slot.aml_len(), bytes.len());
+
+        let uid = 0x1au32;
+        let sta = MethodCall::new("MSTA".into(), vec![&uid]);
+        let crs = MethodCall::new("MCRS".into(), vec![&uid]);
+        let pxm = MethodCall::new("MPXM".into(), vec![&uid]);
+        let ej0 = MethodCall::new("MEJ0".into(), vec![&uid]);
+        let ost = MethodCall::new("MOST".into(), vec![&uid, &Arg(0), &Arg(1), &Arg(2)]);
+        let mut expected = Vec::new();
+        Device::new(
+            "M01A".into(),
+            vec![
+                &Name::new("_HID".into(), &EISAName::new("PNP0C80")),
+                &Name::new("_UID".into(), &uid),
+                &Method::new("_STA".into(), 0, false, vec![&Return::new(&sta)]),
+                &Method::new("_CRS".into(), 0, false, vec![&Return::new(&crs)]),
+                &Method::new("_PXM".into(), 0, false, vec![&Return::new(&pxm)]),
+                &Method::new("_EJ0".into(), 1, false, vec![&ej0]),
+                &Method::new("_OST".into(), 3, false, vec![&ost]),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_proximity_method() {
+        let regions = [
+            HotplugRegion {
+                proximity_domain: 1,
+                base: 0x1_0000_0000,
+                length: 0x4000_0000,
+            },
+            HotplugRegion {
+                proximity_domain: 2,
+                base: 0x1_4000_0000,
+                length: 0x4000_0000,
+            },
+        ];
+        let methods = ControllerMethods {
+            slots: 2,
+            regions: &regions,
+        };
+        let mut bytes = Vec::new();
+        methods.to_aml_bytes(&mut bytes);
+        assert_eq!(methods.aml_len(), bytes.len());
+
+        let lock = Path::new("MLCK");
+        let local = Local(0);
+        let base_high = Path::new("MBHI");
+        let base_low = Path::new("MBLO");
+        let read_base_high = ShiftLeft::new(&local, &base_high, &32u8);
+        let read_base = Or::new(&local, &local, &base_low);
+        let mut expected = Vec::new();
+        Method::new(
+            "MPXM".into(),
+            1,
+            true,
+            vec![
+                &Acquire::new(lock.clone(), 0xffff),
+                &Store::new(&Path::new("MSEL"), &Arg(0)),
+                &read_base_high,
+                &read_base,
+                &Release::new(lock),
+                &If::new(
+                    &LogicalAnd::new(
+                        &GreaterEqual::new(&local, &0x1_0000_0000u64),
+                        &LessEqual::new(&local, &0x1_3fff_ffffu64),
+                    ),
+                    vec![&Return::new(&1u32)],
+                ),
+                &If::new(
+                    &LogicalAnd::new(
+                        &GreaterEqual::new(&local, &0x1_4000_0000u64),
+                        &LessEqual::new(&local, &0x1_7fff_ffffu64),
+                    ),
+                    vec![&Return::new(&2u32)],
+                ),
+                &Return::new(&ZERO),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert!(bytes.windows(expected.len()).any(|w| w == expected));
+    }
+
+    #[test]
+    fn test_memory_hotplug() {
+        let mut hotplug = MemoryHotplug::new(
+            "_SB_.MHPC".into(),
+            MemoryHotplugRegisters::new(0xfed0_d000),
+            4,
+        );
+        hotplug.add_region(1, 0x1_0000_0000, 0x1_0000_0000);
+
+        let mut bytes = Vec::new();
+        hotplug.to_aml_bytes(&mut bytes);
+        assert_eq!(hotplug.aml_len(), bytes.len());
+        for name in [b"MSCN", b"M000", b"M003", b"MBHI", b"MSHI"] {
+            assert!(bytes.windows(4).any(|w| w == name));
+        }
+        assert!(!bytes.windows(4).any(|w| w == b"M004"));
+
+        let mut srat = SRAT::new([1, 2, 3, 4, 5, 6], [1, 2, 3, 4, 5, 6, 7, 8], 1);
+        for affinity in hotplug.memory_affinities() {
+            srat.add_memory_affinity(affinity);
+        }
+        let mut expected = SRAT::new([1, 2, 3, 4, 5, 6], [1, 2, 3, 4, 5, 6, 7, 8], 1);
+        expected.add_memory_affinity(
+            MemoryAffinity::new(1, 0x1_0000_0000, 0x1_0000_0000)
+                .enabled()
+                .hotpluggable(),
+        );
+        let mut srat_bytes = Vec::new();
+        srat.to_aml_bytes(&mut srat_bytes);
+        let mut expected_bytes = Vec::new();
+        expected.to_aml_bytes(&mut expected_bytes);
+        assert_eq!(srat_bytes, expected_bytes);
+    }
+
+    #[test]
+    fn test_region_at_top_of_address_space() {
+        let mut hotplug = MemoryHotplug::new(
+            "_SB_.MHPC".into(),
+            MemoryHotplugRegisters::new(0xfed0_d000),
+            1,
+        );
+        hotplug.add_region(1, 0xffff_ffff_0000_0000, 0x1_0000_0000);
+
+        let mut bytes = Vec::new();
+        hotplug.to_aml_bytes(&mut bytes);
+        let mut end = Vec::new();
+        LessEqual::new(&Local(0), &u64::MAX).to_aml_bytes(&mut end);
+        assert!(bytes.windows(end.len()).any(|w| w == end));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_region_overflow() {
+        let mut hotplug = MemoryHotplug::new(
+            "_SB_.MHPC".into(),
+            MemoryHotplugRegisters::new(0xfed0_d000),
+            1,
+        );
+        hotplug.add_region(1, 0xffff_ffff_0000_0000, 0x1_0000_0001);
+    }
+}
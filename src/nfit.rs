@@ -0,0 +1,980 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::IntoBytes;
+
+extern crate alloc;
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{srat::MemoryAffinity, u8sum, Aml, AmlSink, Checksum, TableHeader};
+
+/// Index of a System Physical Address range structure
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SpaRangeIndex(u16);
+
+/// Index of an NVDIMM control region structure
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ControlRegionIndex(u16);
+
+/// Index of an interleave structure
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InterleaveIndex(u16);
+
+/// NFIT device handle of the NVDIMM in slot `dimm` of the memory
+/// channel `channel`, as also used by the `_ADR` of the NVDIMM devices
+pub fn device_handle(node: u16, socket: u8, controller: u8, channel: u8, dimm: u8) -> u32 {
+    assert!(node < 0x1000);
+    assert!(socket < 16 && controller < 16 && channel < 16 && dimm < 16);
+    (node as u32) << 16
+        | (socket as u32) << 12
+        | (controller as u32) << 8
+        | (channel as u32) << 4
+        | dimm as u32
+}
+
+pub struct NFIT {
+    header: TableHeader,
+    checksum: Checksum,
+    spa_ranges: u16,
+    control_regions: u16,
+    interleaves: u16,
+    structures: Vec<Box<dyn Aml>>,
+}
+
+impl NFIT {
+    pub fn new(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
+        let mut header = TableHeader {
+            signature: *b"NFIT",
+            length: (Self::header_len() as u32).into(),
+            revision: 1,
+            checksum: 0,
+            oem_id,
+            oem_table_id,
+            oem_revision: oem_revision.into(),
+            creator_id: crate::CREATOR_ID,
+            creator_revision: crate::CREATOR_REVISION,
+        };
+
+        let mut cksum = Checksum::default();
+        cksum.append(header.as_bytes());
+        header.checksum = cksum.value();
+
+        Self {
+            header,
+            checksum: cksum,
+            spa_ranges: 0,
+            control_regions: 0,
+            interleaves: 0,
+            structures: Vec::new(),
+        }
+    }
+
+    fn header_len() -> usize {
+        TableHeader::len() + 4
+    }
+
+    fn update_header(&mut self, sum: u8, len: u32) {
+        let old_len = self.header.length.get();
+        let new_len = len + old_len;
+        self.header.length.set(new_len);
+
+        // Remove the bytes from the old length, add the new length
+        // and the new data.
+        self.checksum.delete(old_len.as_bytes());
+        self.checksum.append(new_len.as_bytes());
+        self.checksum.add(sum);
+
+        self.header.checksum = self.checksum.value();
+    }
+
+    fn add_structure(&mut self, structure: Box<dyn Aml>) {
+        self.update_header(u8sum(structure.as_ref()), structure.aml_len() as u32);
+        self.structures.push(structure);
+    }
+
+    // Structure indices start from 1, 0 meaning no structure
+    fn next_index(count: &mut u16) -> u16 {
+        *count += 1;
+        *count
+    }
+
+    pub fn add_spa_range(&mut self, mut range: SpaRange) -> SpaRangeIndex {
+        range.index = Self::next_index(&mut self.spa_ranges);
+        let index = SpaRangeIndex(range.index);
+        self.add_structure(Box::new(range));
+        index
+    }
+
+    pub fn add_memory_device_mapping(&mut self, mapping: MemoryDeviceMapping) {
+        self.add_structure(Box::new(mapping));
+    }
+
+    pub fn add_interleave(&mut self, mut interleave: Interleave) -> InterleaveIndex {
+        interleave.index = Self::next_index(&mut self.interleaves);
+        let index = InterleaveIndex(interleave.index);
+        self.add_structure(Box::new(interleave));
+        index
+    }
+
+    pub fn add_smbios_management_info(&mut self, info: SmbiosManagementInfo) {
+        self.add_structure(Box::new(info));
+    }
+
+    pub fn add_control_region(&mut self, mut region: ControlRegion) -> ControlRegionIndex {
+        region.index = Self::next_index(&mut self.control_regions);
+        let index = ControlRegionIndex(region.index);
+        self.add_structure(Box::new(region));
+        index
+    }
+
+    pub fn add_block_data_window(&mut self, window: BlockDataWindow) {
+        self.add_structure(Box::new(window));
+    }
+
+    pub fn add_flush_hint(&mut self, flush_hint: FlushHint) {
+        self.add_structure(Box::new(flush_hint));
+    }
+
+    pub fn add_platform_capabilities(&mut self, capabilities: PlatformCapabilities) {
+        self.add_structure(Box::new(capabilities));
+    }
+}
+
+/// The NVDIMM Firmware Interface Table describes the NVDIMMs of the
+/// platform, the regions of the system physical address space they
+/// back and how they are interleaved.
+impl Aml for NFIT {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.vec(self.header.as_bytes());
+        sink.dword(0); // reserved
+
+        for st in &self.structures {
+            st.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
+}
+
+#[repr(u16)]
+enum NfitStructureType {
+    SpaRange = 0,
+    MemoryDeviceMapping = 1,
+    Interleave = 2,
+    SmbiosManagementInfo = 3,
+    ControlRegion = 4,
+    BlockDataWindow = 5,
+    FlushHint = 6,
+    PlatformCapabilities = 7,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AddressRangeType {
+    Volatile,
+    Persistent,
+    ControlRegion,
+    BlockDataWindow,
+    VolatileVirtualDisk,
+    VolatileVirtualCd,
+    PersistentVirtualDisk,
+    PersistentVirtualCd,
+}
+
+impl AddressRangeType {
+    fn guid(&self) -> [u8; 16] {
+        match self {
+            // 7305944F-FDDA-44E3-B16C-3F22D252E5D0
+            AddressRangeType::Volatile => [
+                0x4f, 0x94, 0x05, 0x73, 0xda, 0xfd, 0xe3, 0x44, 0xb1, 0x6c, 0x3f, 0x22, 0xd2, 0x52,
+                0xe5, 0xd0,
+            ],
+            // 66F0D379-B4F3-4074-AC43-0D3318B78CDB
+            AddressRangeType::Persistent => [
+                0x79, 0xd3, 0xf0, 0x66, 0xf3, 0xb4, 0x74, 0x40, 0xac, 0x43, 0x0d, 0x33, 0x18, 0xb7,
+                0x8c, 0xdb,
+            ],
+            // 92F701F6-13B4-405D-910B-299367E8234C
+            AddressRangeType::ControlRegion => [
+                0xf6, 0x01, 0xf7, 0x92, 0xb4, 0x13, 0x5d, 0x40, 0x91, 0x0b, 0x29, 0x93, 0x67, 0xe8,
+                0x23, 0x4c,
+            ],
+            // 91AF0530-5D86-470E-A6B0-0A2DB9408249
+            AddressRangeType::BlockDataWindow => [
+                0x30, 0x05, 0xaf, 0x91, 0x86, 0x5d, 0x0e, 0x47, 0xa6, 0xb0, 0x0a, 0x2d, 0xb9, 0x40,
+                0x82, 0x49,
+            ],
+            // 77AB535A-45FC-624B-5560-F7B281D1F96E
+            AddressRangeType::VolatileVirtualDisk => [
+                0x5a, 0x53, 0xab, 0x77, 0xfc, 0x45, 0x4b, 0x62, 0x55, 0x60, 0xf7, 0xb2, 0x81, 0xd1,
+                0xf9, 0x6e,
+            ],
+            // 3D5ABD30-4175-87CE-6D64-D2ADE523C4BB
+            AddressRangeType::VolatileVirtualCd => [
+                0x30, 0xbd, 0x5a, 0x3d, 0x75, 0x41, 0xce, 0x87, 0x6d, 0x64, 0xd2, 0xad, 0xe5, 0x23,
+                0xc4, 0xbb,
+            ],
+            // 5CEA02C9-4D07-69D3-269F-4496FBE096F9
+            AddressRangeType::PersistentVirtualDisk => [
+                0xc9, 0x02, 0xea, 0x5c, 0x07, 0x4d, 0xd3, 0x69, 0x26, 0x9f, 0x44, 0x96, 0xfb, 0xe0,
+                0x96, 0xf9,
+            ],
+            // 08018188-42CD-BB48-100F-5387D53DED3D
+            AddressRangeType::PersistentVirtualCd => [
+                0x88, 0x81, 0x01, 0x08, 0xcd, 0x42, 0x48, 0xbb, 0x10, 0x0f, 0x53, 0x87, 0xd5, 0x3d,
+                0xed, 0x3d,
+            ],
+        }
+    }
+
+    fn is_persistent(&self) -> bool {
+        matches!(
+            self,
+            AddressRangeType::Persistent
+                | AddressRangeType::PersistentVirtualDisk
+                | AddressRangeType::PersistentVirtualCd
+        )
+    }
+}
+
+enum SpaRangeFlags {
+    ManagementOnly = 1 << 0,
+    ProximityDomainValid = 1 << 1,
+    LocationCookieValid = 1 << 2,
+}
+
+/// Range of the system physical address space, such as persistent
+/// memory backed by NVDIMMs
+pub struct SpaRange {
+    index: u16,
+    range_type: AddressRangeType,
+    base_address: u64,
+    length: u64,
+    flags: u16,
+    proximity_domain: u32,
+    mapping_attributes: u64,
+    location_cookie: u64,
+}
+
+impl SpaRange {
+    pub fn new(range_type: AddressRangeType, base_address: u64, length: u64) -> Self {
+        Self {
+            index: 0,
+            range_type,
+            base_address,
+            length,
+            flags: 0,
+            proximity_domain: 0,
+            mapping_attributes: 0,
+            location_cookie: 0,
+        }
+    }
+
+    /// Proximity domain of the range, as in the memory affinity
+    /// structures of the SRAT
+    pub fn proximity_domain(mut self, proximity_domain: u32) -> Self {
+        self.proximity_domain = proximity_domain;
+        self.flags |= SpaRangeFlags::ProximityDomainValid as u16;
+        self
+    }
+
+    /// The control region is only used for management, its block
+    /// control windows being unavailable
+    pub fn management_only(mut self) -> Self {
+        self.flags |= SpaRangeFlags::ManagementOnly as u16;
+        self
+    }
+
+    /// Memory mapping attributes of the range, as the EFI_MEMORY_*
+    /// attributes of the UEFI memory map
+    pub fn mapping_attributes(mut self, attributes: u64) -> Self {
+        self.mapping_attributes = attributes;
+        self
+    }
+
+    pub fn location_cookie(mut self, cookie: u64) -> Self {
+        self.location_cookie = cookie;
+        self.flags |= SpaRangeFlags::LocationCookieValid as u16;
+        self
+    }
+
+    /// Memory affinity structure of the SRAT matching the range, if it
+    /// has a proximity domain
+    pub fn memory_affinity(&self) -> Option<MemoryAffinity> {
+        if self.flags & SpaRangeFlags::ProximityDomainValid as u16 == 0 {
+            return None;
+        }
+
+        let affinity =
+            MemoryAffinity::new(self.proximity_domain, self.base_address, self.length).enabled();
+        if self.range_type.is_persistent() {
+            Some(affinity.nonvolatile())
+        } else {
+            Some(affinity)
+        }
+    }
+
+    fn len() -> usize {
+        64
+    }
+}
+
+impl Aml for SpaRange {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(NfitStructureType::SpaRange as u16);
+        sink.word(Self::len() as u16);
+        sink.word(self.index);
+        sink.word(self.flags);
+        sink.dword(0); // reserved
+        sink.dword(self.proximity_domain);
+        sink.vec(&self.range_type.guid());
+        sink.qword(self.base_address);
+        sink.qword(self.length);
+        sink.qword(self.mapping_attributes);
+        sink.qword(self.location_cookie);
+    }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u16)]
+pub enum NvdimmStateFlags {
+    SaveFailed = 1 << 0,
+    RestoreFailed = 1 << 1,
+    FlushFailed = 1 << 2,
+    NotArmed = 1 << 3,
+    SmartEvents = 1 << 4,
+    SmartNotify = 1 << 5,
+    MapFailed = 1 << 6,
+}
+
+/// Region of an NVDIMM, along with its place in a SPA range
+pub struct MemoryDeviceMapping {
+    device_handle: u32,
+    physical_id: u16,
+    region_id: u16,
+    control_region: ControlRegionIndex,
+    spa_range: u16,
+    region_size: u64,
+    region_offset: u64,
+    physical_address: u64,
+    interleave: u16,
+    interleave_ways: u16,
+    state_flags: u16,
+}
+
+impl MemoryDeviceMapping {
+    /// Region `region_id` of the NVDIMM whose handle is
+    /// `device_handle`, described by `control_region`
+    pub fn new(
+        device_handle: u32,
+        physical_id: u16,
+        region_id: u16,
+        control_region: ControlRegionIndex,
+    ) -> Self {
+        Self {
+            device_handle,
+            physical_id,
+            region_id,
+            control_region,
+            spa_range: 0,
+            region_size: 0,
+            region_offset: 0,
+            physical_address: 0,
+            interleave: 0,
+            interleave_ways: 1,
+            state_flags: 0,
+        }
+    }
+
+    /// The `region_size` bytes of the region starting at
+    /// `physical_address` in the NVDIMM contribute to `spa_range`,
+    /// from `region_offset`
+    pub fn spa_range(
+        mut self,
+        spa_range: SpaRangeIndex,
+        region_size: u64,
+        region_offset: u64,
+        physical_address: u64,
+    ) -> Self {
+        self.spa_range = spa_range.0;
+        self.region_size = region_size;
+        self.region_offset = region_offset;
+        self.physical_address = physical_address;
+        self
+    }
+
+    /// The region is interleaved `ways` ways, following `interleave`
+    pub fn interleave(mut self, interleave: InterleaveIndex, ways: u16) -> Self {
+        assert!(ways > 0);
+        self.interleave = interleave.0;
+        self.interleave_ways = ways;
+        self
+    }
+
+    pub fn state_flag(mut self, flag: NvdimmStateFlags) -> Self {
+        self.state_flags |= flag as u16;
+        self
+    }
+
+    fn len() -> usize {
+        48
+    }
+}
+
+impl Aml for MemoryDeviceMapping {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(NfitStructureType::MemoryDeviceMapping as u16);
+        sink.word(Self::len() as u16);
+        sink.dword(self.device_handle);
+        sink.word(self.physical_id);
+        sink.word(self.region_id);
+        sink.word(self.spa_range);
+        sink.word(self.control_region.0);
+        sink.qword(self.region_size);
+        sink.qword(self.region_offset);
+        sink.qword(self.physical_address);
+        sink.word(self.interleave);
+        sink.word(self.interleave_ways);
+        sink.word(self.state_flags);
+        sink.word(0); // reserved
+    }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
+}
+
+/// Interleave of a region, made of the offsets of its lines in the
+/// NVDIMM, in units of the line size
+pub struct Interleave {
+    index: u16,
+    line_size: u32,
+    line_offsets: Vec<u32>,
+}
+
+impl Interleave {
+    pub fn new(line_size: u32) -> Self {
+        Self {
+            index: 0,
+            line_size,
+            line_offsets: Vec::new(),
+        }
+    }
+
+    pub fn add_line_offset(&mut self, offset: u32) {
+        self.line_offsets.push(offset);
+    }
+
+    fn len(&self) -> usize {
+        16 + 4 * self.line_offsets.len()
+    }
+}
+
+impl Aml for Interleave {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(NfitStructureType::Interleave as u16);
+        sink.word(self.len() as u16);
+        sink.word(self.index);
+        sink.word(0); // reserved
+        sink.dword(self.line_offsets.len() as u32);
+        sink.dword(self.line_size);
+        for offset in &self.line_offsets {
+            sink.dword(*offset);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// SMBIOS structures describing the NVDIMMs
+pub struct SmbiosManagementInfo {
+    data: Vec<u8>,
+}
+
+impl SmbiosManagementInfo {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    fn len(&self) -> usize {
+        8 + self.data.len()
+    }
+}
+
+impl Aml for SmbiosManagementInfo {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(NfitStructureType::SmbiosManagementInfo as u16);
+        sink.word(self.len() as u16);
+        sink.dword(0); // reserved
+        sink.vec(&self.data);
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Block control windows of an NVDIMM control region
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BlockControlWindows {
+    pub count: u16,
+    pub size: u64,
+    pub command_offset: u64,
+    pub command_size: u64,
+    pub status_offset: u64,
+    pub status_size: u64,
+    /// The block data windows are buffered
+    pub buffered: bool,
+}
+
+/// Identification of an NVDIMM and of its management interface,
+/// along with its block control windows if any
+pub struct ControlRegion {
+    index: u16,
+    vendor_id: u16,
+    device_id: u16,
+    revision_id: u16,
+    subsystem: [u16; 3],
+    manufacturing: Option<(u8, u16)>,
+    serial_number: u32,
+    format_interface_code: u16,
+    windows: Option<BlockControlWindows>,
+}
+
+impl ControlRegion {
+    pub fn new(
+        vendor_id: u16,
+        device_id: u16,
+        revision_id: u16,
+        format_interface_code: u16,
+    ) -> Self {
+        Self {
+            index: 0,
+            vendor_id,
+            device_id,
+            revision_id,
+            subsystem: [0; 3],
+            manufacturing: None,
+            serial_number: 0,
+            format_interface_code,
+            windows: None,
+        }
+    }
+
+    pub fn subsystem(mut self, vendor_id: u16, device_id: u16, revision_id: u16) -> Self {
+        self.subsystem = [vendor_id, device_id, revision_id];
+        self
+    }
+
+    pub fn manufacturing(mut self, location: u8, date: u16) -> Self {
+        self.manufacturing = Some((location, date));
+        self
+    }
+
+    pub fn serial_number(mut self, serial_number: u32) -> Self {
+        self.serial_number = serial_number;
+        self
+    }
+
+    pub fn block_control_windows(mut self, windows: BlockControlWindows) -> Self {
+        assert!(windows.count > 0);
+        self.windows = Some(windows);
+        self
+    }
+
+    fn len(&self) -> usize {
+        // The fields describing the windows are omitted without any
+        match self.windows {
+            Some(_) => 80,
+            None => 32,
+        }
+    }
+}
+
+impl Aml for ControlRegion {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let (location, date) = self.manufacturing.unwrap_or_default();
+
+        sink.word(NfitStructureType::ControlRegion as u16);
+        sink.word(self.len() as u16);
+        sink.word(self.index);
+        sink.word(self.vendor_id);
+        sink.word(self.device_id);
+        sink.word(self.revision_id);
+        for id in self.subsystem {
+            sink.word(id);
+        }
+        sink.byte(self.manufacturing.is_some() as u8);
+        sink.byte(location);
+        sink.word(date);
+        sink.word(0); // reserved
+        sink.dword(self.serial_number);
+        sink.word(self.format_interface_code);
+
+        match &self.windows {
+            Some(windows) => {
+                sink.word(windows.count);
+                sink.qword(windows.size);
+                sink.qword(windows.command_offset);
+                sink.qword(windows.command_size);
+                sink.qword(windows.status_offset);
+                sink.qword(windows.status_size);
+                sink.word(windows.buffered as u16);
+                sink.vec(&[0; 6]); // reserved
+            }
+            None => sink.word(0),
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Block data windows of an NVDIMM control region
+pub struct BlockDataWindow {
+    control_region: ControlRegionIndex,
+    count: u16,
+    start_offset: u64,
+    size: u64,
+    capacity: u64,
+    start_address: u64,
+}
+
+impl BlockDataWindow {
+    /// `count` windows of `size` bytes from `start_offset`, giving
+    /// access to the `capacity` bytes of the NVDIMM from
+    /// `start_address`
+    pub fn new(
+        control_region: ControlRegionIndex,
+        count: u16,
+        start_offset: u64,
+        size: u64,
+        capacity: u64,
+        start_address: u64,
+    ) -> Self {
+        Self {
+            control_region,
+            count,
+            start_offset,
+            size,
+            capacity,
+            start_address,
+        }
+    }
+
+    fn len() -> usize {
+        40
+    }
+}
+
+impl Aml for BlockDataWindow {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(NfitStructureType::BlockDataWindow as u16);
+        sink.word(Self::len() as u16);
+        sink.word(self.control_region.0);
+        sink.word(self.count);
+        sink.qword(self.start_offset);
+        sink.qword(self.size);
+        sink.qword(self.capacity);
+        sink.qword(self.start_address);
+    }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
+}
+
+/// Addresses to write to in order to flush the write buffers of an
+/// NVDIMM
+pub struct FlushHint {
+    device_handle: u32,
+    addresses: Vec<u64>,
+}
+
+impl FlushHint {
+    pub fn new(device_handle: u32) -> Self {
+        Self {
+            device_handle,
+            addresses: Vec::new(),
+        }
+    }
+
+    pub fn add_address(&mut self, address: u64) {
+        self.addresses.push(address);
+    }
+
+    fn len(&self) -> usize {
+        16 + 8 * self.addresses.len()
+    }
+}
+
+impl Aml for FlushHint {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(NfitStructureType::FlushHint as u16);
+        sink.word(self.len() as u16);
+        sink.dword(self.device_handle);
+        sink.word(self.addresses.len() as u16);
+        sink.vec(&[0; 6]); // reserved
+        for address in &self.addresses {
+            sink.qword(*address);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PlatformCapability {
+    /// CPU caches are flushed to the NVDIMMs on power loss
+    CpuCacheFlush = 1 << 0,
+    /// Memory controller buffers are flushed to the NVDIMMs on power
+    /// loss
+    MemoryControllerFlush = 1 << 1,
+    HardwareMirroring = 1 << 2,
+}
+
+#[derive(Default)]
+pub struct PlatformCapabilities {
+    capabilities: u32,
+}
+
+impl PlatformCapabilities {
+    // All the capabilities above are reported
+    const HIGHEST_VALID: u8 = 2;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn capability(mut self, capability: PlatformCapability) -> Self {
+        self.capabilities |= capability as u32;
+        self
+    }
+
+    fn len() -> usize {
+        16
+    }
+}
+
+impl Aml for PlatformCapabilities {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.word(NfitStructureType::PlatformCapabilities as u16);
+        sink.word(Self::len() as u16);
+        sink.byte(Self::HIGHEST_VALID);
+        sink.vec(&[0; 3]); // reserved
+        sink.dword(self.capabilities);
+        sink.dword(0); // reserved
+    }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aml::Uuid;
+    use crate::test_utils::check_table;
+
+    #[test]
+    fn test_nfit() {
+        let nfit = NFIT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
+        let bytes = check_table(&nfit);
+        assert_eq!(bytes.len(), 40);
+        assert_eq!(bytes[0..4], *b"NFIT");
+    }
+
+    #[test]
+    fn test_guids() {
+        for (range_type, uuid) in [
+            (
+                AddressRangeType::Volatile,
+                "7305944F-FDDA-44E3-B16C-3F22D252E5D0",
+            ),
+            (
+                AddressRangeType::Persistent,
+                "66F0D379-B4F3-4074-AC43-0D3318B78CDB",
+            ),
+            (
+                AddressRangeType::ControlRegion,
+                "92F701F6-13B4-405D-910B-299367E8234C",
+            ),
+            (
+                AddressRangeType::BlockDataWindow,
+                "91AF0530-5D86-470E-A6B0-0A2DB9408249",
+            ),
+            (
+                AddressRangeType::VolatileVirtualDisk,
+                "77AB535A-45FC-624B-5560-F7B281D1F96E",
+            ),
+            (
+                AddressRangeType::VolatileVirtualCd,
+                "3D5ABD30-4175-87CE-6D64-D2ADE523C4BB",
+            ),
+            (
+                AddressRangeType::PersistentVirtualDisk,
+                "5CEA02C9-4D07-69D3-269F-4496FBE096F9",
+            ),
+            (
+                AddressRangeType::PersistentVirtualCd,
+                "08018188-42CD-BB48-100F-5387D53DED3D",
+            ),
+        ] {
+            let mut bytes = Vec::new();
+            Uuid::new(uuid).to_aml_bytes(&mut bytes);
+            // Skip the buffer header
+            assert_eq!(bytes[4..], range_type.guid());
+        }
+    }
+
+    #[test]
+    fn test_device_handle() {
+        assert_eq!(device_handle(0, 0, 0, 0, 0), 0);
+        assert_eq!(device_handle(0x123, 4, 5, 6, 7), 0x0123_4567);
+    }
+
+    #[test]
+    fn test_pmem() {
+        let mut nfit = NFIT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
+        let range = SpaRange::new(AddressRangeType::Persistent, 0x1_0000_0000, 0x4000_0000)
+            .proximity_domain(1)
+            .mapping_attributes(0x8008);
+        let affinity = range.memory_affinity().unwrap();
+        let spa = nfit.add_spa_range(range);
+        let control = nfit.add_control_region(
+            ControlRegion::new(0x8086, 0x1, 0x1, 0x301).serial_number(0x1234_5678),
+        );
+        nfit.add_memory_device_mapping(MemoryDeviceMapping::new(1, 0, 0, control).spa_range(
+            spa,
+            0x4000_0000,
+            0,
+            0,
+        ));
+        let mut flush_hint = FlushHint::new(1);
+        flush_hint.add_address(0xfed0_2000);
+        nfit.add_flush_hint(flush_hint);
+        nfit.add_platform_capabilities(
+            PlatformCapabilities::new().capability(PlatformCapability::CpuCacheFlush),
+        );
+
+        let bytes = check_table(&nfit);
+        let control_offset = 40 + 64;
+        let mapping_offset = control_offset + 32;
+        let flush_offset = mapping_offset + 48;
+        let caps_offset = flush_offset + 24;
+        assert_eq!(bytes.len(), caps_offset + 16);
+
+        // SPA range 1, with a valid proximity domain
+        assert_eq!(
+            bytes[40..56],
+            [0, 0, 64, 0, 1, 0, 2, 0, 0, 0, 0, 0, 1, 0, 0, 0]
+        );
+        assert_eq!(bytes[56..72], AddressRangeType::Persistent.guid());
+        assert_eq!(bytes[72..80], 0x1_0000_0000u64.to_le_bytes());
+        // Control region 1
+        assert_eq!(
+            bytes[control_offset..control_offset + 12],
+            [4, 0, 32, 0, 1, 0, 0x86, 0x80, 1, 0, 1, 0]
+        );
+        assert_eq!(
+            bytes[control_offset + 24..control_offset + 32],
+            [0x78, 0x56, 0x34, 0x12, 0x01, 0x03, 0, 0]
+        );
+        // Mapping of the NVDIMM to both
+        assert_eq!(
+            bytes[mapping_offset..mapping_offset + 16],
+            [1, 0, 48, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 0]
+        );
+        assert_eq!(bytes[mapping_offset + 42..mapping_offset + 44], [1, 0]);
+        assert_eq!(
+            bytes[flush_offset..flush_offset + 10],
+            [6, 0, 24, 0, 1, 0, 0, 0, 1, 0]
+        );
+        assert_eq!(
+            bytes[caps_offset..],
+            [7, 0, 16, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        let mut expected = Vec::new();
+        MemoryAffinity::new(1, 0x1_0000_0000, 0x4000_0000)
+            .enabled()
+            .nonvolatile()
+            .to_aml_bytes(&mut expected);
+        let mut bytes = Vec::new();
+        affinity.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_block_windows() {
+        let mut nfit = NFIT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
+        let control = nfit.add_control_region(
+            ControlRegion::new(0x8086, 0x1, 0x1, 0x201)
+                .manufacturing(1, 0x1520)
+                .block_control_windows(BlockControlWindows {
+                    count: 1,
+                    size: 0x1000,
+                    command_offset: 0,
+                    command_size: 8,
+                    status_offset: 8,
+                    status_size: 4,
+                    buffered: true,
+                }),
+        );
+        nfit.add_block_data_window(BlockDataWindow::new(
+            control,
+            1,
+            0x1000,
+            0x2000,
+            0x1000_0000,
+            0,
+        ));
+        let mut interleave = Interleave::new(256);
+        interleave.add_line_offset(0);
+        interleave.add_line_offset(2);
+        let interleave = nfit.add_interleave(interleave);
+        nfit.add_memory_device_mapping(
+            MemoryDeviceMapping::new(0x11, 1, 0, control)
+                .interleave(interleave, 2)
+                .state_flag(NvdimmStateFlags::NotArmed),
+        );
+        nfit.add_smbios_management_info(SmbiosManagementInfo::new(alloc::vec![0x11, 0x22]));
+
+        let bytes = check_table(&nfit);
+        let window_offset = 40 + 80;
+        let interleave_offset = window_offset + 40;
+        let mapping_offset = interleave_offset + 24;
+        let smbios_offset = mapping_offset + 48;
+        assert_eq!(bytes.len(), smbios_offset + 10);
+
+        assert_eq!(bytes[40 + 18..40 + 22], [1, 1, 0x20, 0x15]);
+        assert_eq!(bytes[40 + 30..40 + 32], [1, 0]);
+        assert_eq!(bytes[40 + 72..40 + 74], [1, 0]);
+        assert_eq!(bytes[window_offset..window_offset + 6], [5, 0, 40, 0, 1, 0]);
+        assert_eq!(
+            bytes[interleave_offset..],
+            [
+                2, 0, 24, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 2, 0, 0,
+                0, // interleave
+                1, 0, 48, 0, 0x11, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, // mapping
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, //
+                1, 0, 2, 0, 8, 0, 0, 0, // interleave 1, 2 ways, not armed
+                3, 0, 10, 0, 0, 0, 0, 0, 0x11, 0x22, // SMBIOS
+            ]
+        );
+    }
+}
@@ -0,0 +1,378 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{format, vec, vec::Vec};
+
+use crate::{
+    aml::{
+        Acquire, Add, Arg, BufferData, Concat, DeRefOf, Device, Equal, Field, FieldAccessType,
+        FieldEntry, FieldLockRule, FieldUpdateRule, GreaterThan, If, Index, Local, Method,
+        MethodCall, Mid, Mutex, Name, NotEqual, OpRegion, OpRegionSpace, Path, Release, Return,
+        SizeOf, Store, Uuid, While, ONE, ZERO,
+    },
+    Aml, AmlSink,
+};
+
+/// UUID of the `_DSM` functions of the NVDIMM root device
+pub const ROOT_DSM_UUID: &str = "2F10E7A4-9E91-11E4-89D3-123B93F75CBA";
+/// UUID of the `_DSM` functions of the NVDIMM devices, as defined by
+/// the Intel DSM interface
+pub const DIMM_DSM_UUID: &str = "4309AC30-0D11-11E4-9191-0800200C9A66";
+
+/// Handle written to the shared buffer for requests to the root device
+pub const ROOT_HANDLE: u32 = 0x10000;
+/// Function written to the shared buffer to read the NFIT structures
+/// returned by `_FIT`, starting at the byte offset given as argument
+pub const READ_FIT_FUNCTION: u32 = 0xffff_ffff;
+
+/// Size of the buffer shared with the VMM
+pub const BUFFER_SIZE: u32 = 4096;
+
+// The shared buffer holds the handle, revision, function and argument
+// of the request, overwritten by the length and data of the output
+const INPUT_HEADER_SIZE: u32 = 12;
+const OUTPUT_HEADER_SIZE: u32 = 4;
+
+// Method forwarding the _DSM of a device to the VMM, returning a buffer
+// holding a single zero for another UUID
+struct Dsm<'a> {
+    uuid: &'a str,
+    handle: u32,
+}
+
+impl Aml for Dsm<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let uuid = Uuid::new(self.uuid);
+        let unsupported = BufferData::new(vec![0]);
+        // The argument, if any, is the first element of the package
+        let element = Index::new(&ZERO, &Arg(3), &ZERO);
+        let argument = DeRefOf::new(&element);
+        let call = MethodCall::new(
+            "NCAL".into(),
+            vec![&self.handle, &Arg(1), &Arg(2), &Local(0)],
+        );
+
+        Method::new(
+            "_DSM".into(),
+            4,
+            true,
+            vec![
+                &If::new(
+                    &NotEqual::new(&Arg(0), &uuid),
+                    vec![&Return::new(&unsupported)],
+                ),
+                &Store::new(&Local(0), &ZERO),
+                &If::new(
+                    &GreaterThan::new(&SizeOf::new(&Arg(3)), &ZERO),
+                    vec![&Store::new(&Local(0), &argument)],
+                ),
+                &Return::new(&call),
+            ],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+struct Dimm {
+    handle: u32,
+}
+
+impl Aml for Dimm {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let path = Path::new(&format!("NV{:02X}", self.handle & 0xff));
+        let dsm = Dsm {
+            uuid: DIMM_DSM_UUID,
+            handle: self.handle,
+        };
+        Device::new(path, vec![&Name::new("_ADR".into(), &self.handle), &dsm]).to_aml_bytes(sink);
+    }
+}
+
+// Methods of the root device exchanging requests with the VMM
+struct RootMethods;
+
+impl Aml for RootMethods {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let lock = Path::new("NLCK");
+        let handle = Path::new("NHDL");
+        let revision = Path::new("NREV");
+        let function = Path::new("NFUN");
+        let argument = Path::new("NARG");
+        let length = Path::new("NLEN");
+        let output = Path::new("NOUT");
+        let doorbell = Path::new("NDBL");
+
+        // NCAL(handle, revision, function, argument): forward a request
+        // to the VMM and return its output
+        let acquire = Acquire::new(lock.clone(), 0xffff);
+        let release = Release::new(lock);
+        Method::new(
+            "NCAL".into(),
+            4,
+            true,
+            vec![
+                &acquire,
+                &Store::new(&handle, &Arg(0)),
+                &Store::new(&revision, &Arg(1)),
+                &Store::new(&function, &Arg(2)),
+                &Store::new(&argument, &Arg(3)),
+                &Store::new(&doorbell, &ZERO),
+                &Mid::new(&output, &ZERO, &length, &Local(0)),
+                &release,
+                &Return::new(&Local(0)),
+            ],
+        )
+        .to_aml_bytes(sink);
+
+        Dsm {
+            uuid: ROOT_DSM_UUID,
+            handle: ROOT_HANDLE,
+        }
+        .to_aml_bytes(sink);
+
+        // _FIT: read the NFIT structures until the VMM returns no more
+        let read = MethodCall::new(
+            "NCAL".into(),
+            vec![&ROOT_HANDLE, &ONE, &READ_FIT_FUNCTION, &Local(1)],
+        );
+        let done = Return::new(&Local(0));
+        let empty = BufferData::new(vec![]);
+        Method::new(
+            "_FIT".into(),
+            0,
+            true,
+            vec![
+                &Store::new(&Local(0), &empty),
+                &Store::new(&Local(1), &ZERO),
+                &While::new(
+                    &ONE,
+                    vec![
+                        &Store::new(&Local(2), &read),
+                        &Store::new(&Local(3), &SizeOf::new(&Local(2))),
+                        &If::new(&Equal::new(&Local(3), &ZERO), vec![&done]),
+                        &Concat::new(&Local(0), &Local(0), &Local(2)),
+                        &Add::new(&Local(1), &Local(1), &Local(3)),
+                    ],
+                ),
+            ],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+/// NVDIMM root device (ACPI0012), with a child device per NVDIMM. The
+/// `_DSM` methods of the devices and the `_FIT` method of the root
+/// forward their requests to the VMM through a buffer in system
+/// memory, laid out as follows:
+///
+/// | Offset | Input                          | Output                |
+/// |--------|--------------------------------|-----------------------|
+/// | 0x00   | Handle                         | Length of the data    |
+/// | 0x04   | Revision                       | Data                  |
+/// | 0x08   | Function                       |                       |
+/// | 0x0c   | Argument                       |                       |
+///
+/// The handle is the NFIT device handle of the NVDIMM, or
+/// [`ROOT_HANDLE`] for the root device, and the argument is the
+/// contents of the first element of the `_DSM` argument package. The
+/// request is sent by writing to a 32-bit doorbell register, the VMM
+/// filling the output before completing the write. `_FIT` issues
+/// [`READ_FIT_FUNCTION`] requests with the offset to read from, until
+/// no data is returned.
+pub struct Nvdimm {
+    path: Path,
+    buffer_address: u64,
+    doorbell_address: u64,
+    handles: Vec<u32>,
+}
+
+impl Nvdimm {
+    pub fn new(path: Path, buffer_address: u64, doorbell_address: u64) -> Self {
+        Nvdimm {
+            path,
+            buffer_address,
+            doorbell_address,
+            handles: Vec::new(),
+        }
+    }
+
+    /// Add an NVDIMM device, with the device handle used for it in the
+    /// NFIT. The name of the device is derived from the low byte of the
+    /// handle.
+    pub fn add_dimm(&mut self, handle: u32) {
+        assert!(self
+            .handles
+            .iter()
+            .all(|other| other & 0xff != handle & 0xff));
+        self.handles.push(handle);
+    }
+}
+
+impl Aml for Nvdimm {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let hid = Name::new("_HID".into(), &"ACPI0012");
+        let mutex = Mutex::new("NLCK".into(), 0);
+        let buffer = OpRegion::new(
+            "NBUF".into(),
+            OpRegionSpace::SystemMemory,
+            &self.buffer_address,
+            &BUFFER_SIZE,
+        );
+        let input = Field::new(
+            "NBUF".into(),
+            FieldAccessType::DWord,
+            FieldLockRule::NoLock,
+            FieldUpdateRule::Preserve,
+            vec![
+                FieldEntry::Named(*b"NHDL", 32),
+                FieldEntry::Named(*b"NREV", 32),
+                FieldEntry::Named(*b"NFUN", 32),
+                FieldEntry::Named(*b"NARG", ((BUFFER_SIZE - INPUT_HEADER_SIZE) * 8) as usize),
+            ],
+        );
+        let output = Field::new(
+            "NBUF".into(),
+            FieldAccessType::DWord,
+            FieldLockRule::NoLock,
+            FieldUpdateRule::Preserve,
+            vec![
+                FieldEntry::Named(*b"NLEN", 32),
+                FieldEntry::Named(*b"NOUT", ((BUFFER_SIZE - OUTPUT_HEADER_SIZE) * 8) as usize),
+            ],
+        );
+        let doorbell_region = OpRegion::new(
+            "NDRG".into(),
+            OpRegionSpace::SystemMemory,
+            &self.doorbell_address,
+            &4u8,
+        );
+        let doorbell = Field::new(
+            "NDRG".into(),
+            FieldAccessType::DWord,
+            FieldLockRule::NoLock,
+            FieldUpdateRule::Preserve,
+            vec![FieldEntry::Named(*b"NDBL", 32)],
+        );
+        let dimms: Vec<Dimm> = self
+            .handles
+            .iter()
+            .map(|handle| Dimm { handle: *handle })
+            .collect();
+
+        let mut children: Vec<&dyn Aml> = vec![
+            &hid,
+            &mutex,
+            &buffer,
+            &input,
+            &output,
+            &doorbell_region,
+            &doorbell,
+            &RootMethods,
+        ];
+        children.extend(dimms.iter().map(|dimm| dimm as &dyn Aml));
+        Device::new(self.path.clone(), children).to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dimm() {
+        let dimm = Dimm { handle: 0x1 };
+        let mut bytes = Vec::new();
+        dimm.to_aml_bytes(&mut bytes);
+        assert_eq!(dimm.aml_len(), bytes.len());
+
+        let uuid = Uuid::new(DIMM_DSM_UUID);
+        let unsupported = BufferData::new(vec![0]);
+        let element = Index::new(&ZERO, &Arg(3), &ZERO);
+        let argument = DeRefOf::new(&element);
+        let call = MethodCall::new("NCAL".into(), vec![&1u32, &Arg(1), &Arg(2), &Local(0)]);
+        let mut expected = Vec::new();
+        Device::new(
+            "NV01".into(),
+            vec![
+                &Name::new("_ADR".into(), &1u32),
+                &Method::new(
+                    "_DSM".into(),
+                    4,
+                    true,
+                    vec![
+                        &If::new(
+                            &NotEqual::new(&Arg(0), &uuid),
+                            vec![&Return::new(&unsupported)],
+                        ),
+                        &Store::new(&Local(0), &ZERO),
+                        &If::new(
+                            &GreaterThan::new(&SizeOf::new(&Arg(3)), &ZERO),
+                            vec![&Store::new(&Local(0), &argument)],
+                        ),
+                        &Return::new(&call),
+                    ],
+                ),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_call_method() {
+        /*
+        Method (NCAL, 4, Serialized)
+        {
+            Acquire (NLCK, 0xFFFF)
+            NHDL = Arg0
+            NREV = Arg1
+            NFUN = Arg2
+            NARG = Arg3
+            NDBL = Zero
+            Mid (NOUT, Zero, NLEN, Local0)
+            Release (NLCK)
+            Return (Local0)
+        }
+        */
+        let expected = [
+            0x14, 0x3f, 0x4e, 0x43, 0x41, 0x4c, 0x0c, 0x5b, 0x23, 0x4e, 0x4c, 0x43, 0x4b, 0xff,
+            0xff, 0x70, 0x68, 0x4e, 0x48, 0x44, 0x4c, 0x70, 0x69, 0x4e, 0x52, 0x45, 0x56, 0x70,
+            0x6a, 0x4e, 0x46, 0x55, 0x4e, 0x70, 0x6b, 0x4e, 0x41, 0x52, 0x47, 0x70, 0x00, 0x4e,
+            0x44, 0x42, 0x4c, 0x9e, 0x4e, 0x4f, 0x55, 0x54, 0x00, 0x4e, 0x4c, 0x45, 0x4e, 0x60,
+            0x5b, 0x27, 0x4e, 0x4c, 0x43, 0x4b, 0xa4, 0x60,
+        ];
+        let mut bytes = Vec::new();
+        RootMethods.to_aml_bytes(&mut bytes);
+        assert_eq!(RootMethods.aml_len(), bytes.len());
+        assert_eq!(bytes[..expected.len()], expected);
+    }
+
+    #[test]
+    fn test_nvdimm() {
+        let mut nvdimm = Nvdimm::new("_SB_.NVDR".into(), 0xfed1_0000, 0xfed1_1000);
+        nvdimm.add_dimm(0x1);
+        nvdimm.add_dimm(0x10002);
+
+        let mut bytes = Vec::new();
+        nvdimm.to_aml_bytes(&mut bytes);
+        assert_eq!(nvdimm.aml_len(), bytes.len());
+        for name in [b"NVDR", b"_FIT", b"NV01", b"NV02", b"NOUT"] {
+            assert!(bytes.windows(4).any(|w| w == name));
+        }
+
+        let mut dimm = Vec::new();
+        Dimm { handle: 0x10002 }.to_aml_bytes(&mut dimm);
+        assert!(bytes.ends_with(&dimm));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_name() {
+        let mut nvdimm = Nvdimm::new("_SB_.NVDR".into(), 0xfed1_0000, 0xfed1_1000);
+        nvdimm.add_dimm(0x1);
+        nvdimm.add_dimm(0x10001);
+    }
+}
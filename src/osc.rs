@@ -0,0 +1,457 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::vec;
+
+use crate::{
+    aml::{
+        And, Arg, CreateDWordField, Else, Equal, If, Local, Method, NotEqual, Or, Path, Return,
+        Store, Uuid, ONE,
+    },
+    Aml, AmlSink,
+};
+
+/// PCI Host Bridge `_OSC` UUID, see the PCI Firmware Specification
+pub const PCI_HOST_BRIDGE_UUID: &str = "33db4d5b-1ff7-401c-9657-7441c03dd766";
+/// CXL Host Bridge `_OSC` UUID, see the CXL Specification
+pub const CXL_HOST_BRIDGE_UUID: &str = "68f2d50b-c469-4d8a-bd3d-941a103fd3fc";
+/// Platform-Wide `_OSC` UUID
+pub const PLATFORM_UUID: &str = "0811b06e-4a27-44f9-8d60-3cbbc22e7b48";
+
+// Status bits returned in the first DWORD of the capabilities buffer
+const UNRECOGNIZED_UUID: u8 = 1 << 2;
+const UNRECOGNIZED_REVISION: u8 = 1 << 3;
+const CAPABILITIES_MASKED: u8 = 1 << 4;
+
+/// Controls of the PCI Express features that the platform can grant
+/// to the OS
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum PciControl {
+    NativeHotplug = 1 << 0,
+    ShpcNativeHotplug = 1 << 1,
+    Pme = 1 << 2,
+    Aer = 1 << 3,
+    CapabilityStructure = 1 << 4,
+    Ltr = 1 << 5,
+    Dpc = 1 << 7,
+}
+
+/// Controls of the CXL features that the platform can grant to the OS
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum CxlControl {
+    MemoryErrorReporting = 1 << 0,
+}
+
+/// Platform-wide capabilities supported by the platform
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u32)]
+pub enum PlatformCapability {
+    ProcessorAggregatorDevice = 1 << 0,
+    PpcOst = 1 << 1,
+    Pr3 = 1 << 2,
+    HotplugOst = 1 << 3,
+    Apei = 1 << 4,
+    Cpc = 1 << 5,
+    CpcRevision2 = 1 << 6,
+    PlatformCoordinatedLpi = 1 << 7,
+    OsInitiatedLpi = 1 << 8,
+    CpcDiverseHighestPerformance = 1 << 12,
+    GenericInitiator = 1 << 13,
+    CpcFlexibleAddressSpace = 1 << 14,
+    NativeUsb4 = 1 << 18,
+    Prm = 1 << 21,
+    FfhOpRegion = 1 << 22,
+}
+
+// DWORD of the capabilities buffer that is masked against what the
+// platform grants
+struct MaskedDWord {
+    name: &'static str,
+    offset: u8,
+    mask: u32,
+}
+
+// Handling of the capabilities buffer for a single UUID
+struct UuidBranch<'a> {
+    uuid: &'static str,
+    dwords: &'a [MaskedDWord],
+}
+
+impl Aml for UuidBranch<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let cdw1 = Path::new("CDW1");
+
+        If::new(
+            &NotEqual::new(&Arg(1), &ONE),
+            vec![&Or::new(&cdw1, &cdw1, &UNRECOGNIZED_REVISION)],
+        )
+        .to_aml_bytes(sink);
+
+        for (i, dword) in self.dwords.iter().enumerate() {
+            let name = Path::new(dword.name);
+            let requested = Local(i as u8);
+            CreateDWordField::new(&name, &Arg(3), &dword.offset).to_aml_bytes(sink);
+            Store::new(&requested, &name).to_aml_bytes(sink);
+            And::new(&name, &requested, &dword.mask).to_aml_bytes(sink);
+            If::new(
+                &NotEqual::new(&name, &requested),
+                vec![&Or::new(&cdw1, &cdw1, &CAPABILITIES_MASKED)],
+            )
+            .to_aml_bytes(sink);
+        }
+    }
+}
+
+// Chain of If/Else on Arg0 selecting the branch for the UUID
+struct UuidDispatch<'a> {
+    branches: &'a [UuidBranch<'a>],
+}
+
+impl Aml for UuidDispatch<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let Some((branch, others)) = self.branches.split_first() else {
+            let cdw1 = Path::new("CDW1");
+            Or::new(&cdw1, &cdw1, &UNRECOGNIZED_UUID).to_aml_bytes(sink);
+            return;
+        };
+
+        let uuid = Uuid::new(branch.uuid);
+        If::new(&Equal::new(&Arg(0), &uuid), vec![branch]).to_aml_bytes(sink);
+        Else::new(vec![&UuidDispatch { branches: others }]).to_aml_bytes(sink);
+    }
+}
+
+fn osc_method(branches: &[UuidBranch], sink: &mut dyn AmlSink) {
+    let cdw1 = Path::new("CDW1");
+    Method::new(
+        "_OSC".into(),
+        4,
+        false,
+        vec![
+            &CreateDWordField::new(&cdw1, &Arg(3), &0u8),
+            &UuidDispatch { branches },
+            &Return::new(&Arg(3)),
+        ],
+    )
+    .to_aml_bytes(sink);
+}
+
+/// `_OSC` method of a PCI Express (or CXL) host bridge. The controls
+/// requested by the OS are masked with the ones granted by the
+/// platform, and the capabilities buffer is returned to the OS.
+#[derive(Default)]
+pub struct PciHostBridgeOsc {
+    controls: u32,
+    cxl_controls: Option<u32>,
+}
+
+impl PciHostBridgeOsc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the method of a CXL host bridge, which accepts both the
+    /// PCI and the CXL host bridge UUIDs.
+    pub fn new_cxl() -> Self {
+        PciHostBridgeOsc {
+            controls: 0,
+            cxl_controls: Some(0),
+        }
+    }
+
+    pub fn control(mut self, control: PciControl) -> Self {
+        self.controls |= control as u32;
+        self
+    }
+
+    pub(crate) fn grants(&self, control: PciControl) -> bool {
+        self.controls & control as u32 != 0
+    }
+
+    pub fn cxl_control(mut self, control: CxlControl) -> Self {
+        let cxl_controls = self
+            .cxl_controls
+            .as_mut()
+            .expect("CXL controls require a CXL host bridge");
+        *cxl_controls |= control as u32;
+        self
+    }
+}
+
+impl Aml for PciHostBridgeOsc {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let pci_dwords = [MaskedDWord {
+            name: "CDW3",
+            offset: 8,
+            mask: self.controls,
+        }];
+
+        match self.cxl_controls {
+            None => osc_method(
+                &[UuidBranch {
+                    uuid: PCI_HOST_BRIDGE_UUID,
+                    dwords: &pci_dwords,
+                }],
+                sink,
+            ),
+            Some(cxl_controls) => {
+                let cxl_dwords = [
+                    MaskedDWord {
+                        name: "CDW3",
+                        offset: 8,
+                        mask: self.controls,
+                    },
+                    MaskedDWord {
+                        name: "CDW5",
+                        offset: 16,
+                        mask: cxl_controls,
+                    },
+                ];
+                osc_method(
+                    &[
+                        UuidBranch {
+                            uuid: PCI_HOST_BRIDGE_UUID,
+                            dwords: &pci_dwords,
+                        },
+                        UuidBranch {
+                            uuid: CXL_HOST_BRIDGE_UUID,
+                            dwords: &cxl_dwords,
+                        },
+                    ],
+                    sink,
+                )
+            }
+        }
+    }
+}
+
+/// Platform-wide `_OSC` method, to be placed in the `\_SB` scope. The
+/// capabilities that the OS reports are masked with the ones
+/// supported by the platform.
+#[derive(Default)]
+pub struct PlatformOsc {
+    capabilities: u32,
+}
+
+impl PlatformOsc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn capability(mut self, capability: PlatformCapability) -> Self {
+        self.capabilities |= capability as u32;
+        self
+    }
+}
+
+impl Aml for PlatformOsc {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        osc_method(
+            &[UuidBranch {
+                uuid: PLATFORM_UUID,
+                dwords: &[MaskedDWord {
+                    name: "CDW2",
+                    offset: 4,
+                    mask: self.capabilities,
+                }],
+            }],
+            sink,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_pci_host_bridge() {
+        /*
+        Method (_OSC, 4, NotSerialized)  // _OSC: Operating System Capabilities
+        {
+            CreateDWordField (Arg3, Zero, CDW1)
+            If ((Arg0 == ToUUID ("33db4d5b-1ff7-401c-9657-7441c03dd766") /* PCI Host Bridge Device */))
+            {
+                If ((Arg1 != One))
+                {
+                    CDW1 |= 0x08
+                }
+
+                CreateDWordField (Arg3, 0x08, CDW3)
+                Local0 = CDW3
+                CDW3 = (Local0 & 0x1D)
+                If ((CDW3 != Local0))
+                {
+                    CDW1 |= 0x10
+                }
+            }
+            Else
+            {
+                CDW1 |= 0x04
+            }
+
+            Return (Arg3)
+        }
+        */
+        let cdw1 = Path::new("CDW1");
+        let cdw3 = Path::new("CDW3");
+        let uuid = Uuid::new(PCI_HOST_BRIDGE_UUID);
+        let mut expected = Vec::new();
+        Method::new(
+            "_OSC".into(),
+            4,
+            false,
+            vec![
+                &CreateDWordField::new(&cdw1, &Arg(3), &0u8),
+                &If::new(
+                    &Equal::new(&Arg(0), &uuid),
+                    vec![
+                        &If::new(
+                            &NotEqual::new(&Arg(1), &ONE),
+                            vec![&Or::new(&cdw1, &cdw1, &0x08u8)],
+                        ),
+                        &CreateDWordField::new(&cdw3, &Arg(3), &8u8),
+                        &Store::new(&Local(0), &cdw3),
+                        &And::new(&cdw3, &Local(0), &0x1du32),
+                        &If::new(
+                            &NotEqual::new(&cdw3, &Local(0)),
+                            vec![&Or::new(&cdw1, &cdw1, &0x10u8)],
+                        ),
+                    ],
+                ),
+                &Else::new(vec![&Or::new(&cdw1, &cdw1, &0x04u8)]),
+                &Return::new(&Arg(3)),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+
+        let osc = PciHostBridgeOsc::new()
+            .control(PciControl::NativeHotplug)
+            .control(PciControl::Pme)
+            .control(PciControl::Aer)
+            .control(PciControl::CapabilityStructure);
+        let mut bytes = Vec::new();
+        osc.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(osc.aml_len(), bytes.len());
+    }
+
+    #[test]
+    fn test_cxl_host_bridge() {
+        let osc = PciHostBridgeOsc::new_cxl()
+            .control(PciControl::Aer)
+            .cxl_control(CxlControl::MemoryErrorReporting);
+        let mut bytes = Vec::new();
+        osc.to_aml_bytes(&mut bytes);
+        assert_eq!(osc.aml_len(), bytes.len());
+
+        let cdw1 = Path::new("CDW1");
+        let cdw3 = Path::new("CDW3");
+        let cdw5 = Path::new("CDW5");
+        let pci_uuid = Uuid::new(PCI_HOST_BRIDGE_UUID);
+        let cxl_uuid = Uuid::new(CXL_HOST_BRIDGE_UUID);
+        let unknown_revision = NotEqual::new(&Arg(1), &ONE);
+        let set_unknown_revision = Or::new(&cdw1, &cdw1, &0x08u8);
+        let revision_check = If::new(&unknown_revision, vec![&set_unknown_revision]);
+        let cdw3_masked = NotEqual::new(&cdw3, &Local(0));
+        let set_masked = Or::new(&cdw1, &cdw1, &0x10u8);
+        let masked = If::new(&cdw3_masked, vec![&set_masked]);
+        let mut expected = Vec::new();
+        Method::new(
+            "_OSC".into(),
+            4,
+            false,
+            vec![
+                &CreateDWordField::new(&cdw1, &Arg(3), &0u8),
+                &If::new(
+                    &Equal::new(&Arg(0), &pci_uuid),
+                    vec![
+                        &revision_check,
+                        &CreateDWordField::new(&cdw3, &Arg(3), &8u8),
+                        &Store::new(&Local(0), &cdw3),
+                        &And::new(&cdw3, &Local(0), &0x8u8),
+                        &masked,
+                    ],
+                ),
+                &Else::new(vec![
+                    &If::new(
+                        &Equal::new(&Arg(0), &cxl_uuid),
+                        vec![
+                            &revision_check,
+                            &CreateDWordField::new(&cdw3, &Arg(3), &8u8),
+                            &Store::new(&Local(0), &cdw3),
+                            &And::new(&cdw3, &Local(0), &0x8u8),
+                            &masked,
+                            &CreateDWordField::new(&cdw5, &Arg(3), &16u8),
+                            &Store::new(&Local(1), &cdw5),
+                            &And::new(&cdw5, &Local(1), &1u8),
+                            &If::new(
+                                &NotEqual::new(&cdw5, &Local(1)),
+                                vec![&Or::new(&cdw1, &cdw1, &0x10u8)],
+                            ),
+                        ],
+                    ),
+                    &Else::new(vec![&Or::new(&cdw1, &cdw1, &0x04u8)]),
+                ]),
+                &Return::new(&Arg(3)),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cxl_control_without_cxl() {
+        PciHostBridgeOsc::new().cxl_control(CxlControl::MemoryErrorReporting);
+    }
+
+    #[test]
+    fn test_platform() {
+        let osc = PlatformOsc::new()
+            .capability(PlatformCapability::HotplugOst)
+            .capability(PlatformCapability::GenericInitiator);
+        let mut bytes = Vec::new();
+        osc.to_aml_bytes(&mut bytes);
+        assert_eq!(osc.aml_len(), bytes.len());
+
+        let cdw1 = Path::new("CDW1");
+        let cdw2 = Path::new("CDW2");
+        let uuid = Uuid::new(PLATFORM_UUID);
+        let mut expected = Vec::new();
+        Method::new(
+            "_OSC".into(),
+            4,
+            false,
+            vec![
+                &CreateDWordField::new(&cdw1, &Arg(3), &0u8),
+                &If::new(
+                    &Equal::new(&Arg(0), &uuid),
+                    vec![
+                        &If::new(
+                            &NotEqual::new(&Arg(1), &ONE),
+                            vec![&Or::new(&cdw1, &cdw1, &0x08u8)],
+                        ),
+                        &CreateDWordField::new(&cdw2, &Arg(3), &4u8),
+                        &Store::new(&Local(0), &cdw2),
+                        &And::new(&cdw2, &Local(0), &0x2008u32),
+                        &If::new(
+                            &NotEqual::new(&cdw2, &Local(0)),
+                            vec![&Or::new(&cdw1, &cdw1, &0x10u8)],
+                        ),
+                    ],
+                ),
+                &Else::new(vec![&Or::new(&cdw1, &cdw1, &0x04u8)]),
+                &Return::new(&Arg(3)),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+}
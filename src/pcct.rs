@@ -0,0 +1,657 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::{
+    byteorder::{self, LE},
+    Immutable, IntoBytes,
+};
+
+extern crate alloc;
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    gas::{AddressSpace, GAS},
+    madt::Trigger,
+    u8sum, Aml, AmlSink, Checksum, TableHeader,
+};
+
+type U16 = byteorder::U16<LE>;
+type U32 = byteorder::U32<LE>;
+type U64 = byteorder::U64<LE>;
+
+// Register descriptor, as in aml::Register
+const REGDESC: u8 = 0x82;
+
+/// Index of a subspace in the PCCT, as used by `PCC` operation
+/// regions and by registers in the PCC address space
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SubspaceId(u8);
+
+impl SubspaceId {
+    pub fn id(&self) -> u8 {
+        self.0
+    }
+
+    /// Register at byte `offset` in the communication space of the
+    /// subspace, e.g. for the `_CPC` entries
+    pub fn register(&self, bit_width: u8, bit_offset: u8, offset: u64) -> PccRegister {
+        PccRegister {
+            subspace: *self,
+            bit_width,
+            bit_offset,
+            offset,
+        }
+    }
+}
+
+impl Aml for SubspaceId {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        self.0.to_aml_bytes(sink);
+    }
+}
+
+/// Register descriptor in the PCC address space. Its access size
+/// field holds the subspace ID, so it cannot be described with a
+/// [`GAS`].
+#[derive(Copy, Clone, Debug)]
+pub struct PccRegister {
+    subspace: SubspaceId,
+    bit_width: u8,
+    bit_offset: u8,
+    offset: u64,
+}
+
+impl Aml for PccRegister {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.byte(REGDESC);
+        sink.word(0x0c); // length
+        sink.byte(AddressSpace::PlatformCommunicationsChannel as u8);
+        sink.byte(self.bit_width);
+        sink.byte(self.bit_offset);
+        sink.byte(self.subspace.0);
+        sink.qword(self.offset);
+    }
+}
+
+pub struct PCCT {
+    header: TableHeader,
+    checksum: Checksum,
+    flags: u32,
+    subspaces: Vec<Box<dyn Aml>>,
+}
+
+impl PCCT {
+    const SUBSPACE_OFFSET: u32 = 48;
+    const MAX_SUBSPACES: usize = 256;
+
+    pub fn new(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
+        let mut header = TableHeader {
+            signature: *b"PCCT",
+            length: Self::SUBSPACE_OFFSET.into(),
+            revision: 2,
+            checksum: 0,
+            oem_id,
+            oem_table_id,
+            oem_revision: oem_revision.into(),
+            creator_id: crate::CREATOR_ID,
+            creator_revision: crate::CREATOR_REVISION,
+        };
+
+        let mut cksum = Checksum::default();
+        cksum.append(header.as_bytes());
+        header.checksum = cksum.value();
+
+        Self {
+            header,
+            checksum: cksum,
+            flags: 0,
+            subspaces: Vec::new(),
+        }
+    }
+
+    /// The platform raises an SCI on command completion for the
+    /// generic subspaces
+    pub fn platform_interrupt(mut self) -> Self {
+        self.checksum.delete(self.flags.as_bytes());
+        self.flags |= 1;
+        self.checksum.append(self.flags.as_bytes());
+        self.header.checksum = self.checksum.value();
+        self
+    }
+
+    fn update_header(&mut self, sum: u8, len: u32) {
+        let old_len = self.header.length.get();
+        let new_len = len + old_len;
+        self.header.length.set(new_len);
+
+        // Remove the bytes from the old length, add the new length
+        // and the new data.
+        self.checksum.delete(old_len.as_bytes());
+        self.checksum.append(new_len.as_bytes());
+        self.checksum.add(sum);
+        self.header.checksum = self.checksum.value();
+    }
+
+    fn add_subspace(&mut self, subspace: Box<dyn Aml>) -> SubspaceId {
+        assert!(self.subspaces.len() < Self::MAX_SUBSPACES);
+        let id = SubspaceId(self.subspaces.len() as u8);
+        self.update_header(u8sum(subspace.as_ref()), subspace.aml_len() as u32);
+        self.subspaces.push(subspace);
+        id
+    }
+
+    pub fn add_generic(&mut self, subspace: GenericSubspace) -> SubspaceId {
+        self.add_subspace(Box::new(subspace))
+    }
+
+    pub fn add_hw_reduced(&mut self, subspace: HwReducedSubspace) -> SubspaceId {
+        self.add_subspace(Box::new(subspace))
+    }
+
+    pub fn add_extended(&mut self, subspace: ExtendedSubspace) -> SubspaceId {
+        self.add_subspace(Box::new(subspace))
+    }
+
+    pub fn add_hw_register(&mut self, subspace: HwRegisterSubspace) -> SubspaceId {
+        self.add_subspace(Box::new(subspace))
+    }
+}
+
+/// The Platform Communications Channel Table describes the subspaces
+/// through which OSPM exchanges commands with the platform, e.g. for
+/// CPPC or firmware-managed RAS.
+impl Aml for PCCT {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.vec(self.header.as_bytes());
+        sink.dword(self.flags);
+        sink.qword(0); // reserved
+
+        for subspace in &self.subspaces {
+            subspace.to_aml_bytes(sink);
+        }
+    }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
+}
+
+/// Register updated with a read-modify-write: the value read is
+/// and-ed with `preserve`, then or-ed with `write`
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Doorbell {
+    pub register: GAS,
+    pub preserve: u64,
+    pub write: u64,
+}
+
+impl Doorbell {
+    pub fn new(register: GAS, preserve: u64, write: u64) -> Self {
+        Self {
+            register,
+            preserve,
+            write,
+        }
+    }
+}
+
+fn interrupt_flags(trigger: Trigger, active_low: bool) -> u8 {
+    let mut flags = 0;
+    if active_low {
+        flags |= 1 << 0;
+    }
+    if matches!(trigger, Trigger::Edge) {
+        flags |= 1 << 1;
+    }
+    flags
+}
+
+/// Generic communications subspace (type 0), completion being
+/// signalled through the SCI when the table has
+/// [`PCCT::platform_interrupt`] set
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, IntoBytes, Immutable)]
+pub struct GenericSubspace {
+    subspace_type: u8,
+    length: u8,
+    _reserved: [u8; 6],
+    base_address: U64,
+    memory_length: U64,
+    doorbell_register: GAS,
+    doorbell_preserve: U64,
+    doorbell_write: U64,
+    nominal_latency: U32,
+    max_periodic_access_rate: U32,
+    min_request_turnaround: U16,
+}
+
+crate::assert_same_size!(GenericSubspace, [u8; 62]);
+
+impl GenericSubspace {
+    /// Shared memory region at `base_address`, `length` bytes long
+    pub fn new(base_address: u64, length: u64, doorbell: Doorbell) -> Self {
+        Self {
+            subspace_type: 0,
+            length: core::mem::size_of::<Self>() as u8,
+            base_address: base_address.into(),
+            memory_length: length.into(),
+            doorbell_register: doorbell.register,
+            doorbell_preserve: doorbell.preserve.into(),
+            doorbell_write: doorbell.write.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Latency in microseconds, maximum number of accesses per minute
+    /// (0 when unlimited) and minimum delay between two commands in
+    /// microseconds
+    pub fn timing(
+        mut self,
+        nominal_latency: u32,
+        max_periodic_access_rate: u32,
+        min_request_turnaround: u16,
+    ) -> Self {
+        self.nominal_latency = nominal_latency.into();
+        self.max_periodic_access_rate = max_periodic_access_rate.into();
+        self.min_request_turnaround = min_request_turnaround.into();
+        self
+    }
+}
+
+crate::aml_as_bytes!(GenericSubspace);
+
+/// HW-reduced communications subspace, with a platform interrupt for
+/// completion. This is a type 1 subspace, or type 2 once
+/// [`HwReducedSubspace::acknowledge`] gives the register used to
+/// acknowledge the interrupt.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, IntoBytes, Immutable)]
+pub struct HwReducedSubspace {
+    subspace_type: u8,
+    length: u8,
+    platform_interrupt: U32,
+    platform_interrupt_flags: u8,
+    _reserved: u8,
+    base_address: U64,
+    memory_length: U64,
+    doorbell_register: GAS,
+    doorbell_preserve: U64,
+    doorbell_write: U64,
+    nominal_latency: U32,
+    max_periodic_access_rate: U32,
+    min_request_turnaround: U16,
+    // Type 2 only
+    ack_register: GAS,
+    ack_preserve: U64,
+    ack_write: U64,
+}
+
+crate::assert_same_size!(HwReducedSubspace, [u8; 90]);
+
+impl HwReducedSubspace {
+    const TYPE1_LEN: u8 = 62;
+
+    /// Shared memory region at `base_address`, `length` bytes long,
+    /// completion being signalled by the `gsiv` interrupt
+    pub fn new(
+        base_address: u64,
+        length: u64,
+        doorbell: Doorbell,
+        gsiv: u32,
+        trigger: Trigger,
+        active_low: bool,
+    ) -> Self {
+        Self {
+            subspace_type: 1,
+            length: Self::TYPE1_LEN,
+            platform_interrupt: gsiv.into(),
+            platform_interrupt_flags: interrupt_flags(trigger, active_low),
+            base_address: base_address.into(),
+            memory_length: length.into(),
+            doorbell_register: doorbell.register,
+            doorbell_preserve: doorbell.preserve.into(),
+            doorbell_write: doorbell.write.into(),
+            ..Default::default()
+        }
+    }
+
+    /// See [`GenericSubspace::timing`]
+    pub fn timing(
+        mut self,
+        nominal_latency: u32,
+        max_periodic_access_rate: u32,
+        min_request_turnaround: u16,
+    ) -> Self {
+        self.nominal_latency = nominal_latency.into();
+        self.max_periodic_access_rate = max_periodic_access_rate.into();
+        self.min_request_turnaround = min_request_turnaround.into();
+        self
+    }
+
+    pub fn acknowledge(mut self, ack: Doorbell) -> Self {
+        self.subspace_type = 2;
+        self.length = core::mem::size_of::<Self>() as u8;
+        self.ack_register = ack.register;
+        self.ack_preserve = ack.preserve.into();
+        self.ack_write = ack.write.into();
+        self
+    }
+}
+
+impl Aml for HwReducedSubspace {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        sink.vec(&self.as_bytes()[..self.length as usize]);
+    }
+
+    fn aml_len(&self) -> usize {
+        self.length as usize
+    }
+}
+
+/// Extended PCC subspace, either an initiator subspace (type 3) where
+/// OSPM sends commands to the platform, or a responder subspace
+/// (type 4) where the platform sends notifications to OSPM
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, IntoBytes, Immutable)]
+pub struct ExtendedSubspace {
+    subspace_type: u8,
+    length: u8,
+    platform_interrupt: U32,
+    platform_interrupt_flags: u8,
+    _reserved0: u8,
+    base_address: U64,
+    memory_length: U32,
+    doorbell_register: GAS,
+    doorbell_preserve: U64,
+    doorbell_write: U64,
+    nominal_latency: U32,
+    max_periodic_access_rate: U32,
+    min_request_turnaround: U32,
+    ack_register: GAS,
+    ack_preserve: U64,
+    ack_set: U64,
+    _reserved1: [u8; 8],
+    cmd_complete_check_register: GAS,
+    cmd_complete_check_mask: U64,
+    cmd_update_register: GAS,
+    cmd_update_preserve: U64,
+    cmd_update_set: U64,
+    error_status_register: GAS,
+    error_status_mask: U64,
+}
+
+crate::assert_same_size!(ExtendedSubspace, [u8; 164]);
+
+impl ExtendedSubspace {
+    fn new(subspace_type: u8, base_address: u64, length: u32, doorbell: Doorbell) -> Self {
+        Self {
+            subspace_type,
+            length: core::mem::size_of::<Self>() as u8,
+            base_address: base_address.into(),
+            memory_length: length.into(),
+            doorbell_register: doorbell.register,
+            doorbell_preserve: doorbell.preserve.into(),
+            doorbell_write: doorbell.write.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Shared memory region at `base_address`, `length` bytes long
+    pub fn initiator(base_address: u64, length: u32, doorbell: Doorbell) -> Self {
+        Self::new(3, base_address, length, doorbell)
+    }
+
+    /// Shared memory region at `base_address`, `length` bytes long,
+    /// `doorbell` being used by OSPM to signal the completion of a
+    /// notification
+    pub fn responder(base_address: u64, length: u32, doorbell: Doorbell) -> Self {
+        Self::new(4, base_address, length, doorbell)
+    }
+
+    pub fn platform_interrupt(mut self, gsiv: u32, trigger: Trigger, active_low: bool) -> Self {
+        self.platform_interrupt = gsiv.into();
+        self.platform_interrupt_flags = interrupt_flags(trigger, active_low);
+        self
+    }
+
+    /// See [`GenericSubspace::timing`]
+    pub fn timing(
+        mut self,
+        nominal_latency: u32,
+        max_periodic_access_rate: u32,
+        min_request_turnaround: u32,
+    ) -> Self {
+        self.nominal_latency = nominal_latency.into();
+        self.max_periodic_access_rate = max_periodic_access_rate.into();
+        self.min_request_turnaround = min_request_turnaround.into();
+        self
+    }
+
+    /// Register used to acknowledge the platform interrupt
+    pub fn acknowledge(mut self, ack: Doorbell) -> Self {
+        self.ack_register = ack.register;
+        self.ack_preserve = ack.preserve.into();
+        self.ack_set = ack.write.into();
+        self
+    }
+
+    /// Register whose bits in `mask` are set once a command completes
+    pub fn command_complete(mut self, register: GAS, mask: u64) -> Self {
+        self.cmd_complete_check_register = register;
+        self.cmd_complete_check_mask = mask.into();
+        self
+    }
+
+    /// Register updated to flag a command as complete
+    pub fn command_update(mut self, update: Doorbell) -> Self {
+        self.cmd_update_register = update.register;
+        self.cmd_update_preserve = update.preserve.into();
+        self.cmd_update_set = update.write.into();
+        self
+    }
+
+    /// Register whose bits in `mask` are set on an error
+    pub fn error_status(mut self, register: GAS, mask: u64) -> Self {
+        self.error_status_register = register;
+        self.error_status_mask = mask.into();
+        self
+    }
+}
+
+crate::aml_as_bytes!(ExtendedSubspace);
+
+/// HW register-based communications subspace (type 5), where the
+/// commands are exchanged through registers instead of a shared
+/// memory region with a PCC signature
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, IntoBytes, Immutable)]
+pub struct HwRegisterSubspace {
+    subspace_type: u8,
+    length: u8,
+    version: U16,
+    base_address: U64,
+    memory_length: U64,
+    doorbell_register: GAS,
+    doorbell_preserve: U64,
+    doorbell_write: U64,
+    cmd_complete_check_register: GAS,
+    cmd_complete_check_mask: U64,
+    error_status_register: GAS,
+    error_status_mask: U64,
+    nominal_latency: U32,
+    min_request_turnaround: U32,
+}
+
+crate::assert_same_size!(HwRegisterSubspace, [u8; 96]);
+
+impl HwRegisterSubspace {
+    /// Register block at `base_address`, `length` bytes long
+    pub fn new(base_address: u64, length: u64, doorbell: Doorbell) -> Self {
+        Self {
+            subspace_type: 5,
+            length: core::mem::size_of::<Self>() as u8,
+            base_address: base_address.into(),
+            memory_length: length.into(),
+            doorbell_register: doorbell.register,
+            doorbell_preserve: doorbell.preserve.into(),
+            doorbell_write: doorbell.write.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Latency and minimum delay between two commands, both in
+    /// microseconds
+    pub fn timing(mut self, nominal_latency: u32, min_request_turnaround: u32) -> Self {
+        self.nominal_latency = nominal_latency.into();
+        self.min_request_turnaround = min_request_turnaround.into();
+        self
+    }
+
+    /// Register whose bits in `mask` are set once a command completes
+    pub fn command_complete(mut self, register: GAS, mask: u64) -> Self {
+        self.cmd_complete_check_register = register;
+        self.cmd_complete_check_mask = mask.into();
+        self
+    }
+
+    /// Register whose bits in `mask` are set on an error
+    pub fn error_status(mut self, register: GAS, mask: u64) -> Self {
+        self.error_status_register = register;
+        self.error_status_mask = mask.into();
+        self
+    }
+}
+
+crate::aml_as_bytes!(HwRegisterSubspace);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas::AccessSize;
+    use crate::test_utils::check_table;
+
+    fn doorbell(address: u64) -> Doorbell {
+        Doorbell::new(
+            GAS::new(
+                AddressSpace::SystemMemory,
+                32,
+                0,
+                AccessSize::DwordAccess,
+                address,
+            ),
+            0,
+            1,
+        )
+    }
+
+    #[test]
+    fn test_pcct() {
+        let pcct = PCCT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef).platform_interrupt();
+        let bytes = check_table(&pcct);
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(bytes[0..4], *b"PCCT");
+        assert_eq!(bytes[36..48], [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_subspaces() {
+        let mut pcct = PCCT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
+        let generic = pcct.add_generic(
+            GenericSubspace::new(0x1000_0000, 0x1000, doorbell(0x2000_0000)).timing(1000, 0, 10),
+        );
+        let type1 = pcct.add_hw_reduced(HwReducedSubspace::new(
+            0x1000_1000,
+            0x1000,
+            doorbell(0x2000_0004),
+            40,
+            Trigger::Edge,
+            true,
+        ));
+        let type2 = pcct.add_hw_reduced(
+            HwReducedSubspace::new(
+                0x1000_2000,
+                0x1000,
+                doorbell(0x2000_0008),
+                41,
+                Trigger::Level,
+                false,
+            )
+            .acknowledge(doorbell(0x2000_000c)),
+        );
+        let type3 = pcct.add_extended(
+            ExtendedSubspace::initiator(0x1000_3000, 0x1000, doorbell(0x2000_0010))
+                .platform_interrupt(42, Trigger::Level, false)
+                .command_complete(doorbell(0x2000_0014).register, 1)
+                .error_status(doorbell(0x2000_0018).register, 4),
+        );
+        let type4 = pcct.add_extended(
+            ExtendedSubspace::responder(0x1000_4000, 0x1000, doorbell(0x2000_001c))
+                .command_update(doorbell(0x2000_0020)),
+        );
+        let type5 = pcct.add_hw_register(
+            HwRegisterSubspace::new(0x1000_5000, 0x100, doorbell(0x2000_0024)).timing(100, 5),
+        );
+
+        assert_eq!(
+            [generic, type1, type2, type3, type4, type5].map(|s| s.id()),
+            [0, 1, 2, 3, 4, 5]
+        );
+
+        let bytes = check_table(&pcct);
+        let mut offset = 48;
+        for (subspace_type, len) in [(0, 62), (1, 62), (2, 90), (3, 164), (4, 164), (5, 96)] {
+            assert_eq!(bytes[offset..offset + 2], [subspace_type, len]);
+            offset += len as usize;
+        }
+        assert_eq!(bytes.len(), offset);
+
+        // Type 0 timing
+        assert_eq!(
+            bytes[48 + 52..48 + 62],
+            [0xe8, 0x03, 0, 0, 0, 0, 0, 0, 10, 0]
+        );
+        // Type 1 interrupt, active low and edge triggered
+        assert_eq!(bytes[110 + 2..110 + 8], [40, 0, 0, 0, 3, 0]);
+        // Type 2 acknowledge register
+        assert_eq!(
+            bytes[172 + 62..172 + 74],
+            [0, 32, 0, 3, 0x0c, 0x00, 0x00, 0x20, 0, 0, 0, 0]
+        );
+        // Type 3 error status mask
+        assert_eq!(bytes[262 + 156..262 + 164], [4, 0, 0, 0, 0, 0, 0, 0]);
+        // Type 5 timing
+        assert_eq!(bytes[590 + 88..590 + 96], [100, 0, 0, 0, 5, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_pcc_register() {
+        /*
+        Register (PCC, 0x20, 0x00, 0x0000000000000008, 0x01)
+        */
+        let mut pcct = PCCT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
+        pcct.add_generic(GenericSubspace::new(
+            0x1000_0000,
+            0x1000,
+            doorbell(0x2000_0000),
+        ));
+        let subspace = pcct.add_hw_reduced(HwReducedSubspace::new(
+            0x1000_1000,
+            0x1000,
+            doorbell(0x2000_0004),
+            40,
+            Trigger::Level,
+            false,
+        ));
+
+        let register = subspace.register(32, 0, 8);
+        let mut bytes = Vec::new();
+        register.to_aml_bytes(&mut bytes);
+        assert_eq!(
+            bytes,
+            [0x82, 0x0c, 0x00, 0x0a, 0x20, 0x00, 0x01, 0x08, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(register.aml_len(), bytes.len());
+    }
+}
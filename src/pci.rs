@@ -0,0 +1,370 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    aml::{AddressSpace, AddressSpaceCacheable, Device, EISAName, Name, Path, ResourceTemplate},
+    mcfg::MCFG,
+    osc::{PciControl, PciHostBridgeOsc},
+    pcihp::PciHotplug,
+    prt::Prt,
+    Aml, AmlSink,
+};
+
+/// PCI Express root bridge (PNP0A08) device, describing the bus range
+/// and the address windows decoded by the bridge. The ECAM region of
+/// the bus range is reserved through a motherboard resources device
+/// (PNP0C02) nested in the bridge.
+pub struct PciHostBridge {
+    path: Path,
+    segment: u16,
+    uid: u32,
+    ecam_base: u64,
+    start_bus: u8,
+    end_bus: u8,
+    io: Option<(u16, u16)>,
+    mmio32: Option<(u32, u32)>,
+    mmio64: Option<(u64, u64)>,
+    prt: Option<Prt>,
+    osc: Option<PciHostBridgeOsc>,
+    hotplug: Option<PciHotplug>,
+}
+
+impl PciHostBridge {
+    /// Create a root bridge for buses `start_bus` to `end_bus` of
+    /// `segment`. `ecam_base` is the address of the configuration
+    /// space of bus 0, as reported in the MCFG table. The `_UID` of
+    /// the bridge defaults to its segment.
+    pub fn new(path: Path, segment: u16, ecam_base: u64, start_bus: u8, end_bus: u8) -> Self {
+        assert!(start_bus <= end_bus);
+        assert!(ecam_base
+            .checked_add(((end_bus as u64 + 1) << 20) - 1)
+            .is_some());
+        PciHostBridge {
+            path,
+            segment,
+            uid: segment.into(),
+            ecam_base,
+            start_bus,
+            end_bus,
+            io: None,
+            mmio32: None,
+            mmio64: None,
+            prt: None,
+            osc: None,
+            hotplug: None,
+        }
+    }
+
+    pub fn uid(mut self, uid: u32) -> Self {
+        self.uid = uid;
+        self
+    }
+
+    /// IO port window forwarded to the root bus
+    pub fn io(mut self, base: u16, size: u16) -> Self {
+        assert!(size > 0);
+        assert!(base.checked_add(size - 1).is_some());
+        self.io = Some((base, size));
+        self
+    }
+
+    /// MMIO window below 4GiB forwarded to the root bus
+    pub fn mmio32(mut self, base: u32, size: u32) -> Self {
+        assert!(size > 0);
+        assert!(base.checked_add(size - 1).is_some());
+        self.mmio32 = Some((base, size));
+        self
+    }
+
+    /// MMIO window above 4GiB forwarded to the root bus
+    pub fn mmio64(mut self, base: u64, size: u64) -> Self {
+        assert!(size > 0);
+        assert!(base.checked_add(size - 1).is_some());
+        self.mmio64 = Some((base, size));
+        self
+    }
+
+    pub fn prt(mut self, prt: Prt) -> Self {
+        self.prt = Some(prt);
+        self
+    }
+
+    /// `_OSC` of the bridge. Native hotplug cannot be granted along
+    /// with ACPI-based hotplug.
+    pub fn osc(mut self, osc: PciHostBridgeOsc) -> Self {
+        assert!(self.hotplug.is_none() || !osc.grants(PciControl::NativeHotplug));
+        self.osc = Some(osc);
+        self
+    }
+
+    /// ACPI-based hotplug of the slots of the root bus
+    pub fn hotplug(mut self, hotplug: PciHotplug) -> Self {
+        assert!(!self
+            .osc
+            .as_ref()
+            .is_some_and(|osc| osc.grants(PciControl::NativeHotplug)));
+        self.hotplug = Some(hotplug);
+        self
+    }
+
+    /// Add the ECAM entry describing this root bridge to `mcfg`.
+    pub fn add_ecam(&self, mcfg: &mut MCFG) {
+        mcfg.add_ecam(self.ecam_base, self.segment, self.start_bus, self.end_bus);
+    }
+
+    // Configuration space of the bus range of the bridge
+    fn ecam_range(&self) -> (u64, u64) {
+        let start = self.ecam_base + ((self.start_bus as u64) << 20);
+        let end = self.ecam_base + ((self.end_bus as u64 + 1) << 20) - 1;
+        (start, end)
+    }
+}
+
+impl Aml for PciHostBridge {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let hid = Name::new("_HID".into(), &EISAName::new("PNP0A08"));
+        let cid = Name::new("_CID".into(), &EISAName::new("PNP0A03"));
+        let seg = Name::new("_SEG".into(), &self.segment);
+        let uid = Name::new("_UID".into(), &self.uid);
+        let bbn = Name::new("_BBN".into(), &self.start_bus);
+
+        let bus_range =
+            AddressSpace::<u16>::new_bus_number(self.start_bus.into(), self.end_bus.into());
+        let io = self
+            .io
+            .map(|(base, size)| AddressSpace::<u16>::new_io(base, base + (size - 1), None));
+        let mmio32 = self.mmio32.map(|(base, size)| {
+            AddressSpace::<u32>::new_memory(
+                AddressSpaceCacheable::NotCacheable,
+                true,
+                base,
+                base + (size - 1),
+                None,
+            )
+        });
+        let mmio64 = self.mmio64.map(|(base, size)| {
+            AddressSpace::<u64>::new_memory(
+                AddressSpaceCacheable::NotCacheable,
+                true,
+                base,
+                base + (size - 1),
+                None,
+            )
+        });
+        let mut resources: Vec<&dyn Aml> = vec![&bus_range];
+        if let Some(io) = &io {
+            resources.push(io);
+        }
+        if let Some(mmio32) = &mmio32 {
+            resources.push(mmio32);
+        }
+        if let Some(mmio64) = &mmio64 {
+            resources.push(mmio64);
+        }
+        let crs = Name::new("_CRS".into(), &ResourceTemplate::new(resources));
+
+        let (ecam_start, ecam_end) = self.ecam_range();
+        let ecam = AddressSpace::<u64>::new_memory(
+            AddressSpaceCacheable::NotCacheable,
+            true,
+            ecam_start,
+            ecam_end,
+            None,
+        );
+        let res_hid = Name::new("_HID".into(), &EISAName::new("PNP0C02"));
+        let res_crs = Name::new("_CRS".into(), &ResourceTemplate::new(vec![&ecam]));
+        let reserved = Device::new("RES0".into(), vec![&res_hid, &res_crs]);
+
+        let mut children: Vec<&dyn Aml> = vec![&hid, &cid, &seg, &uid, &bbn, &crs];
+        if let Some(prt) = &self.prt {
+            children.push(prt);
+        }
+        if let Some(osc) = &self.osc {
+            children.push(osc);
+        }
+        if let Some(hotplug) = &self.hotplug {
+            children.push(hotplug);
+        }
+        children.push(&reserved);
+
+        Device::new(self.path.clone(), children).to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        aml::OpRegionSpace,
+        pcihp::PciHotplugRegisters,
+        prt::{InterruptSource, Swizzle},
+    };
+
+    #[test]
+    fn test_host_bridge() {
+        let mut prt = Prt::new();
+        prt.add_slots(
+            0..32,
+            Swizzle::Standard,
+            &[32, 33, 34, 35].map(InterruptSource::Gsi),
+        );
+        let osc = PciHostBridgeOsc::new().control(PciControl::NativeHotplug);
+
+        let bridge = PciHostBridge::new("_SB_.PCI1".into(), 1, 0xe000_0000, 0x10, 0x1f)
+            .io(0x1000, 0x1000)
+            .mmio32(0xc000_0000, 0x1000_0000)
+            .mmio64(0x80_0000_0000, 0x40_0000_0000)
+            .prt(prt)
+            .osc(osc);
+
+        let mut bytes = Vec::new();
+        bridge.to_aml_bytes(&mut bytes);
+        assert_eq!(bridge.aml_len(), bytes.len());
+
+        let mut expected_prt = Prt::new();
+        expected_prt.add_slots(
+            0..32,
+            Swizzle::Standard,
+            &[32, 33, 34, 35].map(InterruptSource::Gsi),
+        );
+        let expected_osc = PciHostBridgeOsc::new().control(PciControl::NativeHotplug);
+        let mut expected = Vec::new();
+        Device::new(
+            "_SB_.PCI1".into(),
+            vec![
+                &Name::new("_HID".into(), &EISAName::new("PNP0A08")),
+                &Name::new("_CID".into(), &EISAName::new("PNP0A03")),
+                &Name::new("_SEG".into(), &1u16),
+                &Name::new("_UID".into(), &1u32),
+                &Name::new("_BBN".into(), &0x10u8),
+                &Name::new(
+                    "_CRS".into(),
+                    &ResourceTemplate::new(vec![
+                        &AddressSpace::new_bus_number(0x10u16, 0x1fu16),
+                        &AddressSpace::new_io(0x1000u16, 0x1fffu16, None),
+                        &AddressSpace::new_memory(
+                            AddressSpaceCacheable::NotCacheable,
+                            true,
+                            0xc000_0000u32,
+                            0xcfff_ffffu32,
+                            None,
+                        ),
+                        &AddressSpace::new_memory(
+                            AddressSpaceCacheable::NotCacheable,
+                            true,
+                            0x80_0000_0000u64,
+                            0xbf_ffff_ffffu64,
+                            None,
+                        ),
+                    ]),
+                ),
+                &expected_prt,
+                &expected_osc,
+                &Device::new(
+                    "RES0".into(),
+                    vec![
+                        &Name::new("_HID".into(), &EISAName::new("PNP0C02")),
+                        &Name::new(
+                            "_CRS".into(),
+                            &ResourceTemplate::new(vec![&AddressSpace::new_memory(
+                                AddressSpaceCacheable::NotCacheable,
+                                true,
+                                0xe100_0000u64,
+                                0xe1ff_ffffu64,
+                                None,
+                            )]),
+                        ),
+                    ],
+                ),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_minimal_host_bridge() {
+        let bridge = PciHostBridge::new("PCI0".into(), 0, 0xb000_0000, 0, 0xff).uid(7);
+        let mut bytes = Vec::new();
+        bridge.to_aml_bytes(&mut bytes);
+        assert_eq!(bridge.aml_len(), bytes.len());
+        assert_eq!(bridge.ecam_range(), (0xb000_0000, 0xbfff_ffff));
+
+        let mut expected = Vec::new();
+        Device::new(
+            "PCI0".into(),
+            vec![
+                &Name::new("_HID".into(), &EISAName::new("PNP0A08")),
+                &Name::new("_CID".into(), &EISAName::new("PNP0A03")),
+                &Name::new("_SEG".into(), &0u16),
+                &Name::new("_UID".into(), &7u32),
+                &Name::new("_BBN".into(), &0u8),
+                &Name::new(
+                    "_CRS".into(),
+                    &ResourceTemplate::new(vec![&AddressSpace::new_bus_number(0u16, 0xffu16)]),
+                ),
+                &Device::new(
+                    "RES0".into(),
+                    vec![
+                        &Name::new("_HID".into(), &EISAName::new("PNP0C02")),
+                        &Name::new(
+                            "_CRS".into(),
+                            &ResourceTemplate::new(vec![&AddressSpace::new_memory(
+                                AddressSpaceCacheable::NotCacheable,
+                                true,
+                                0xb000_0000u64,
+                                0xbfff_ffffu64,
+                                None,
+                            )]),
+                        ),
+                    ],
+                ),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_mcfg_entry() {
+        let bridge = PciHostBridge::new("PCI1".into(), 3, 0xe000_0000, 0x10, 0x1f);
+        let mut mcfg = MCFG::new([1, 2, 3, 4, 5, 6], [1, 2, 3, 4, 5, 6, 7, 8], 1);
+        bridge.add_ecam(&mut mcfg);
+
+        let mut bytes = Vec::new();
+        mcfg.to_aml_bytes(&mut bytes);
+        // Header and reserved bytes, followed by the ECAM entry
+        assert_eq!(
+            bytes[44..],
+            [0x00, 0x00, 0x00, 0xe0, 0, 0, 0, 0, 3, 0, 0x10, 0x1f, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_io_window_overflow() {
+        PciHostBridge::new("PCI0".into(), 0, 0xb000_0000, 0, 0xff).io(0xf000, 0x2000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ecam_overflow() {
+        PciHostBridge::new("PCI0".into(), 0, 0xffff_ffff_f100_0000, 0, 0xff);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_native_and_acpi_hotplug() {
+        PciHostBridge::new("PCI0".into(), 0, 0xb000_0000, 0, 0xff)
+            .osc(PciHostBridgeOsc::new().control(PciControl::NativeHotplug))
+            .hotplug(PciHotplug::new(PciHotplugRegisters::new(
+                OpRegionSpace::SystemIO,
+                0xae00,
+            )));
+    }
+}
@@ -0,0 +1,313 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{format, vec, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    aml::{
+        register_field, And, Arg, Device, If, Local, Method, MethodCall, Name, Notify, OpRegion,
+        OpRegionSpace, Path, Return, ShiftLeft, Store, ONE,
+    },
+    Aml, AmlSink,
+};
+
+/// Layout of the register block through which the VMM signals
+/// hotplug events on the slots of a PCI bus. Each register is a
+/// 32-bit bitmap, with bit n referring to slot n.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PciHotplugRegisters {
+    /// Address space of the block, either `SystemIO` or `SystemMemory`
+    pub space: OpRegionSpace,
+    /// Base address of the block
+    pub address: u64,
+    /// Size of the block
+    pub length: u32,
+    /// Offset of the bitmap of the slots a device was plugged into,
+    /// expected to clear itself when read
+    pub up: u32,
+    /// Offset of the bitmap of the slots whose device is to be
+    /// unplugged, expected to clear itself when read
+    pub down: u32,
+    /// Offset of the bitmap written by the OS to eject the device of a
+    /// slot
+    pub eject: u32,
+}
+
+impl PciHotplugRegisters {
+    /// Default layout, with the up, down and eject bitmaps at offsets
+    /// 0, 4 and 8.
+    pub fn new(space: OpRegionSpace, address: u64) -> Self {
+        PciHotplugRegisters {
+            space,
+            address,
+            length: 12,
+            up: 0,
+            down: 4,
+            eject: 8,
+        }
+    }
+}
+
+fn slot_name(slot: u8) -> Path {
+    Path::new(&format!("S{slot:02X}_"))
+}
+
+// Device of a single slot. The slot is always reported present, the OS
+// probing it to find whether a device is plugged in.
+struct Slot {
+    slot: u8,
+}
+
+impl Aml for Slot {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        // Function 0 of the device
+        let address = (self.slot as u32) << 16;
+        let sun = Path::new("_SUN");
+        let eject = MethodCall::new("PCEJ".into(), vec![&sun]);
+        Device::new(
+            slot_name(self.slot),
+            vec![
+                &Name::new("_ADR".into(), &address),
+                &Name::new("_SUN".into(), &self.slot),
+                &Method::new("_STA".into(), 0, false, vec![&Return::new(&0xfu8)]),
+                &Method::new("_EJ0".into(), 1, false, vec![&eject]),
+            ],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+// Notify the slot when its bit is set in the up (Local0) or down
+// (Local1) bitmap
+struct SlotNotify {
+    slot: u8,
+}
+
+impl Aml for SlotNotify {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let mask = 1u32 << self.slot;
+        let slot = slot_name(self.slot);
+        // Device check
+        If::new(
+            &And::new(&Local(2), &Local(0), &mask),
+            vec![&Notify::new(&slot, &ONE)],
+        )
+        .to_aml_bytes(sink);
+        // Eject request
+        If::new(
+            &And::new(&Local(2), &Local(1), &mask),
+            vec![&Notify::new(&slot, &3u8)],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+/// ACPI-based hotplug of the slots of a PCI bus, for guests not using
+/// native PCI Express hotplug. This generates a device per slot, with
+/// an `_EJ0` method writing to the eject bitmap, along with a `PCNT`
+/// method notifying the OS of the events reported in the up and down
+/// bitmaps. These objects belong to the scope of the bridge device, see
+/// [`PciHostBridge::hotplug`](crate::pci::PciHostBridge::hotplug), and
+/// `PCNT` is meant to be called when the VMM signals a PCI hotplug
+/// event, e.g. through a GED.
+pub struct PciHotplug {
+    registers: PciHotplugRegisters,
+    slots: Vec<u8>,
+}
+
+impl PciHotplug {
+    pub fn new(registers: PciHotplugRegisters) -> Self {
+        PciHotplug {
+            registers,
+            slots: Vec::new(),
+        }
+    }
+
+    pub fn add_slot(&mut self, slot: u8) {
+        assert!(slot < 32);
+        assert!(!self.slots.contains(&slot));
+        self.slots.push(slot);
+    }
+
+    pub fn add_slots(&mut self, slots: Range<u8>) {
+        for slot in slots {
+            self.add_slot(slot);
+        }
+    }
+}
+
+impl Aml for PciHotplug {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let region = OpRegion::new(
+            "PCST".into(),
+            self.registers.space,
+            &self.registers.address,
+            &self.registers.length,
+        );
+        let up_field = register_field("PCST", *b"PCIU", self.registers.up);
+        let down_field = register_field("PCST", *b"PCID", self.registers.down);
+        let eject_field = register_field("PCST", *b"B0EJ", self.registers.eject);
+
+        // PCEJ(slot): eject the device of the slot
+        let eject = Path::new("B0EJ");
+        let write_eject = ShiftLeft::new(&eject, &ONE, &Arg(0));
+        let pcej = Method::new("PCEJ".into(), 1, true, vec![&write_eject]);
+
+        // PCNT(): notify the OS of the pending events
+        let up = Path::new("PCIU");
+        let down = Path::new("PCID");
+        let read_up = Store::new(&Local(0), &up);
+        let read_down = Store::new(&Local(1), &down);
+        let notifies: Vec<SlotNotify> = self
+            .slots
+            .iter()
+            .map(|slot| SlotNotify { slot: *slot })
+            .collect();
+        let mut pcnt_body: Vec<&dyn Aml> = vec![&read_up, &read_down];
+        pcnt_body.extend(notifies.iter().map(|n| n as &dyn Aml));
+        let pcnt = Method::new("PCNT".into(), 0, true, pcnt_body);
+
+        region.to_aml_bytes(sink);
+        up_field.to_aml_bytes(sink);
+        down_field.to_aml_bytes(sink);
+        eject_field.to_aml_bytes(sink);
+        pcej.to_aml_bytes(sink);
+        pcnt.to_aml_bytes(sink);
+        for slot in &self.slots {
+            Slot { slot: *slot }.to_aml_bytes(sink);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pci::PciHostBridge;
+
+    #[test]
+    fn test_slot() {
+        /*
+        Device (S1F_)
+        {
+            Name (_ADR, 0x001F0000)  // _ADR: Address
+            Name (_SUN, 0x1F)  // _SUN: Slot User Number
+            Method (_STA, 0, NotSerialized)  // _STA: Status
+            {
+                Return (0x0F)
+            }
+
+            Method (_EJ0, 1, NotSerialized)  // _EJ0: Eject Device, x=0-9
+            {
+                PCEJ (_SUN)
+            }
+        }
+        */
+        let expected = [
+            0x5b, 0x82, 0x2f, 0x53, 0x31, 0x46, 0x5f, 0x08, 0x5f, 0x41, 0x44, 0x52, 0x0c, 0x00,
+            0x00, 0x1f, 0x00, 0x08, 0x5f, 0x53, 0x55, 0x4e, 0x0a, 0x1f, 0x14, 0x09, 0x5f, 0x53,
+            0x54, 0x41, 0x00, 0xa4, 0x0a, 0x0f, 0x14, 0x0e, 0x5f, 0x45, 0x4a, 0x30, 0x01, 0x50,
+            0x43, 0x45, 0x4a, 0x5f, 0x53, 0x55, 0x4e,
+        ];
+        let slot = Slot { slot: 0x1f };
+        let mut bytes = Vec::new();
+        slot.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(slot.aml_len(), bytes.len());
+    }
+
+    #[test]
+    fn test_pci_hotplug() {
+        let mut hotplug =
+            PciHotplug::new(PciHotplugRegisters::new(OpRegionSpace::SystemIO, 0xae00));
+        hotplug.add_slots(1..3);
+
+        let mut bytes = Vec::new();
+        hotplug.to_aml_bytes(&mut bytes);
+        assert_eq!(hotplug.aml_len(), bytes.len());
+
+        let b0ej = Path::new("B0EJ");
+        let s01 = Path::new("S01_");
+        let s02 = Path::new("S02_");
+        let mut expected = Vec::new();
+        OpRegion::new("PCST".into(), OpRegionSpace::SystemIO, &0xae00u64, &12u32)
+            .to_aml_bytes(&mut expected);
+        register_field("PCST", *b"PCIU", 0).to_aml_bytes(&mut expected);
+        register_field("PCST", *b"PCID", 4).to_aml_bytes(&mut expected);
+        register_field("PCST", *b"B0EJ", 8).to_aml_bytes(&mut expected);
+        Method::new(
+            "PCEJ".into(),
+            1,
+            true,
+            vec![&ShiftLeft::new(&b0ej, &ONE, &Arg(0))],
+        )
+        .to_aml_bytes(&mut expected);
+        Method::new(
+            "PCNT".into(),
+            0,
+            true,
+            vec![
+                &Store::new(&Local(0), &Path::new("PCIU")),
+                &Store::new(&Local(1), &Path::new("PCID")),
+                &If::new(
+                    &And::new(&Local(2), &Local(0), &2u32),
+                    vec![&Notify::new(&s01, &ONE)],
+                ),
+                &If::new(
+                    &And::new(&Local(2), &Local(1), &2u32),
+                    vec![&Notify::new(&s01, &3u8)],
+                ),
+                &If::new(
+                    &And::new(&Local(2), &Local(0), &4u32),
+                    vec![&Notify::new(&s02, &ONE)],
+                ),
+                &If::new(
+                    &And::new(&Local(2), &Local(1), &4u32),
+                    vec![&Notify::new(&s02, &3u8)],
+                ),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        Slot { slot: 1 }.to_aml_bytes(&mut expected);
+        Slot { slot: 2 }.to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_host_bridge() {
+        let mut hotplug = PciHotplug::new(PciHotplugRegisters::new(
+            OpRegionSpace::SystemMemory,
+            0xfe00_0000,
+        ));
+        hotplug.add_slot(4);
+        let bridge = PciHostBridge::new("PCI0".into(), 0, 0xb000_0000, 0, 0xff).hotplug(hotplug);
+
+        let mut bytes = Vec::new();
+        bridge.to_aml_bytes(&mut bytes);
+        assert_eq!(bridge.aml_len(), bytes.len());
+
+        let mut slot = Vec::new();
+        Slot { slot: 4 }.to_aml_bytes(&mut slot);
+        assert!(bytes.windows(slot.len()).any(|w| w == slot));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_slot() {
+        let mut hotplug =
+            PciHotplug::new(PciHotplugRegisters::new(OpRegionSpace::SystemIO, 0xae00));
+        hotplug.add_slot(32);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_slot() {
+        let mut hotplug =
+            PciHotplug::new(PciHotplugRegisters::new(OpRegionSpace::SystemIO, 0xae00));
+        hotplug.add_slots(0..4);
+        hotplug.add_slot(2);
+    }
+}
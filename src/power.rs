@@ -0,0 +1,539 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{
+    aml::{
+        register_field, And, Device, EISAName, If, Index, Local, Method, Name, OpRegion,
+        OpRegionSpace, Package, Path, Return, Store, ONE, ZERO,
+    },
+    Aml, AmlSink,
+};
+
+// Element of a package returned by a method
+enum Element {
+    // Value of a register, read when the method is evaluated
+    Register(&'static str),
+    Integer(u32),
+    String(String),
+}
+
+impl Aml for Element {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        match self {
+            // Placeholder, replaced by the value of the register
+            Element::Register(_) => ZERO.to_aml_bytes(sink),
+            Element::Integer(value) => value.to_aml_bytes(sink),
+            Element::String(value) => value.to_aml_bytes(sink),
+        }
+    }
+}
+
+// Method returning a package whose elements are partly read from
+// registers, stored in the package object `name` before returning it
+struct RegisterPackage {
+    method: &'static str,
+    name: &'static str,
+    elements: Vec<Element>,
+}
+
+// Store the register of the element at `index` into the package
+struct StoreElement<'a> {
+    package: &'a Path,
+    index: usize,
+    register: &'static str,
+}
+
+impl Aml for StoreElement<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let register = Path::new(self.register);
+        let element = Index::new(&ZERO, self.package, &self.index);
+        Store::new(&element, &register).to_aml_bytes(sink);
+    }
+}
+
+impl Aml for RegisterPackage {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let package = Path::new(self.name);
+        let initial = Package::new(self.elements.iter().map(|e| e as &dyn Aml).collect());
+        let name = Name::new(package.clone(), &initial);
+        let stores: Vec<StoreElement> = self
+            .elements
+            .iter()
+            .enumerate()
+            .filter_map(|(index, element)| match element {
+                Element::Register(register) => Some(StoreElement {
+                    package: &package,
+                    index,
+                    register,
+                }),
+                _ => None,
+            })
+            .collect();
+        let ret = Return::new(&package);
+
+        let mut body: Vec<&dyn Aml> = vec![&name];
+        body.extend(stores.iter().map(|s| s as &dyn Aml));
+        body.push(&ret);
+        Method::new(self.method.into(), 0, true, body).to_aml_bytes(sink);
+    }
+}
+
+/// Rechargeable battery (PNP0C0A) whose state is read from a block
+/// of 32-bit registers in system memory. Capacities are in mWh, rates
+/// in mW and voltages in mV, the value 0xffffffff meaning unknown.
+///
+/// | Offset | Register                                                 |
+/// |--------|----------------------------------------------------------|
+/// | 0x00   | Bit 0 set when the battery is present                    |
+/// | 0x04   | State: bit 0 discharging, bit 1 charging, bit 2 critical |
+/// | 0x08   | Present rate                                             |
+/// | 0x0c   | Remaining capacity                                       |
+/// | 0x10   | Present voltage                                          |
+/// | 0x14   | Design capacity                                          |
+/// | 0x18   | Last full charge capacity                                |
+/// | 0x1c   | Design voltage                                           |
+/// | 0x20   | Cycle count                                              |
+pub struct Battery {
+    path: Path,
+    uid: u32,
+    address: u64,
+    warning_capacity: u32,
+    low_capacity: u32,
+    model: String,
+    serial: String,
+    battery_type: String,
+    oem: String,
+}
+
+impl Battery {
+    /// Notify value for a change of the `_BST` status
+    pub const STATUS_CHANGED: u8 = 0x80;
+    /// Notify value for a change of the `_BIF` and `_BIX` information
+    pub const INFORMATION_CHANGED: u8 = 0x81;
+
+    pub fn new(path: Path, uid: u32, address: u64) -> Self {
+        Battery {
+            path,
+            uid,
+            address,
+            warning_capacity: 0,
+            low_capacity: 0,
+            model: String::new(),
+            serial: String::new(),
+            battery_type: String::new(),
+            oem: String::new(),
+        }
+    }
+
+    /// Remaining capacities at which the OS warns about the battery
+    /// running low, and at which it considers it critically low
+    pub fn capacity_levels(mut self, warning: u32, low: u32) -> Self {
+        self.warning_capacity = warning;
+        self.low_capacity = low;
+        self
+    }
+
+    pub fn model(mut self, model: &str) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    pub fn serial(mut self, serial: &str) -> Self {
+        self.serial = serial.into();
+        self
+    }
+
+    /// Battery type, e.g. "LION"
+    pub fn battery_type(mut self, battery_type: &str) -> Self {
+        self.battery_type = battery_type.into();
+        self
+    }
+
+    pub fn oem(mut self, oem: &str) -> Self {
+        self.oem = oem.into();
+        self
+    }
+
+    // Power unit (mWh), design capacity, last full charge capacity,
+    // technology (rechargeable) and design voltage, shared by _BIF and
+    // _BIX
+    fn capacity_elements(&self) -> Vec<Element> {
+        vec![
+            Element::Integer(0),
+            Element::Register("BDCP"),
+            Element::Register("BLFC"),
+            Element::Integer(1),
+            Element::Register("BDVT"),
+            Element::Integer(self.warning_capacity),
+            Element::Integer(self.low_capacity),
+        ]
+    }
+
+    // Granularities, followed by the strings, shared by _BIF and _BIX
+    fn string_elements(&self) -> Vec<Element> {
+        vec![
+            Element::Integer(1),
+            Element::Integer(1),
+            Element::String(self.model.clone()),
+            Element::String(self.serial.clone()),
+            Element::String(self.battery_type.clone()),
+            Element::String(self.oem.clone()),
+        ]
+    }
+
+    fn bif(&self) -> RegisterPackage {
+        let mut elements = self.capacity_elements();
+        elements.extend(self.string_elements());
+        RegisterPackage {
+            method: "_BIF",
+            name: "BIFP",
+            elements,
+        }
+    }
+
+    fn bix(&self) -> RegisterPackage {
+        let unknown = 0xffff_ffff;
+        // Revision 1
+        let mut elements = vec![Element::Integer(1)];
+        elements.extend(self.capacity_elements());
+        elements.extend([
+            Element::Register("BCYC"),
+            // Measurement accuracy of 100%
+            Element::Integer(100_000),
+            // Sampling times and averaging intervals
+            Element::Integer(unknown),
+            Element::Integer(unknown),
+            Element::Integer(unknown),
+            Element::Integer(unknown),
+        ]);
+        elements.extend(self.string_elements());
+        // Not swappable
+        elements.push(Element::Integer(0));
+        RegisterPackage {
+            method: "_BIX",
+            name: "BIXP",
+            elements,
+        }
+    }
+
+    fn bst(&self) -> RegisterPackage {
+        RegisterPackage {
+            method: "_BST",
+            name: "BSTP",
+            elements: vec![
+                Element::Register("BSTA"),
+                Element::Register("BRTE"),
+                Element::Register("BRCP"),
+                Element::Register("BVLT"),
+            ],
+        }
+    }
+}
+
+impl Aml for Battery {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let hid = Name::new("_HID".into(), &EISAName::new("PNP0C0A"));
+        let uid = Name::new("_UID".into(), &self.uid);
+        let pcl_scope = Path::new("\\_SB_");
+        let pcl = Name::new("_PCL".into(), &Package::new(vec![&pcl_scope]));
+        let registers = RegisterBlock {
+            region: "BATR",
+            address: self.address,
+            names: &[
+                *b"BPRS", *b"BSTA", *b"BRTE", *b"BRCP", *b"BVLT", *b"BDCP", *b"BLFC", *b"BDVT",
+                *b"BCYC",
+            ],
+        };
+
+        // Present and functioning, or only functioning
+        let present = Path::new("BPRS");
+        let present_bit = And::new(&Local(0), &present, &ONE);
+        let return_present = Return::new(&0x1fu8);
+        let if_present = If::new(&present_bit, vec![&return_present]);
+        let return_absent = Return::new(&0x0fu8);
+        let sta = Method::new("_STA".into(), 0, false, vec![&if_present, &return_absent]);
+
+        let bif = self.bif();
+        let bix = self.bix();
+        let bst = self.bst();
+        Device::new(
+            self.path.clone(),
+            vec![&hid, &uid, &pcl, &registers, &sta, &bif, &bix, &bst],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+// Region of 32-bit registers in system memory, along with the fields
+// giving access to them in order
+struct RegisterBlock<'a> {
+    region: &'a str,
+    address: u64,
+    names: &'a [[u8; 4]],
+}
+
+impl Aml for RegisterBlock<'_> {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let length = self.names.len() as u32 * 4;
+        OpRegion::new(
+            self.region.into(),
+            OpRegionSpace::SystemMemory,
+            &self.address,
+            &length,
+        )
+        .to_aml_bytes(sink);
+        for (index, name) in self.names.iter().enumerate() {
+            register_field(self.region, *name, index as u32 * 4).to_aml_bytes(sink);
+        }
+    }
+}
+
+// Method returning bit 0 of the register `name`
+struct RegisterBit {
+    method: &'static str,
+    register: &'static str,
+}
+
+impl Aml for RegisterBit {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let register = Path::new(self.register);
+        let bit = And::new(&Local(0), &register, &ONE);
+        Method::new(self.method.into(), 0, false, vec![&Return::new(&bit)]).to_aml_bytes(sink);
+    }
+}
+
+/// AC adapter (ACPI0003) whose state is read from bit 0 of a 32-bit
+/// register in system memory, set when the adapter is online.
+pub struct AcAdapter {
+    path: Path,
+    address: u64,
+}
+
+impl AcAdapter {
+    /// Notify value for a change of the `_PSR` power source state
+    pub const STATUS_CHANGED: u8 = 0x80;
+
+    pub fn new(path: Path, address: u64) -> Self {
+        AcAdapter { path, address }
+    }
+}
+
+impl Aml for AcAdapter {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let hid = Name::new("_HID".into(), &"ACPI0003");
+        let pcl_scope = Path::new("\\_SB_");
+        let pcl = Name::new("_PCL".into(), &Package::new(vec![&pcl_scope]));
+        let registers = RegisterBlock {
+            region: "ACR_",
+            address: self.address,
+            names: &[*b"ACST"],
+        };
+        let psr = RegisterBit {
+            method: "_PSR",
+            register: "ACST",
+        };
+        Device::new(self.path.clone(), vec![&hid, &pcl, &registers, &psr]).to_aml_bytes(sink);
+    }
+}
+
+/// Lid (PNP0C0D) whose state is read from bit 0 of a 32-bit register
+/// in system memory, set when the lid is open.
+pub struct Lid {
+    path: Path,
+    address: u64,
+}
+
+impl Lid {
+    /// Notify value for a change of the `_LID` state
+    pub const STATUS_CHANGED: u8 = 0x80;
+
+    pub fn new(path: Path, address: u64) -> Self {
+        Lid { path, address }
+    }
+}
+
+impl Aml for Lid {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let hid = Name::new("_HID".into(), &EISAName::new("PNP0C0D"));
+        let registers = RegisterBlock {
+            region: "LIDR",
+            address: self.address,
+            names: &[*b"LIDS"],
+        };
+        let lid = RegisterBit {
+            method: "_LID",
+            register: "LIDS",
+        };
+        Device::new(self.path.clone(), vec![&hid, &registers, &lid]).to_aml_bytes(sink);
+    }
+}
+
+/// Control method power (PNP0C0C) or sleep (PNP0C0E) button
+pub struct Button {
+    path: Path,
+    hid: &'static str,
+}
+
+impl Button {
+    /// Notify value signaling a press of the button
+    pub const PRESSED: u8 = 0x80;
+
+    pub fn power(path: Path) -> Self {
+        Button {
+            path,
+            hid: "PNP0C0C",
+        }
+    }
+
+    pub fn sleep(path: Path) -> Self {
+        Button {
+            path,
+            hid: "PNP0C0E",
+        }
+    }
+}
+
+impl Aml for Button {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        Device::new(
+            self.path.clone(),
+            vec![&Name::new("_HID".into(), &EISAName::new(self.hid))],
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_button() {
+        /*
+        Device (PWRB)
+        {
+            Name (_HID, EisaId ("PNP0C0C") /* Power Button Device */)  // _HID: Hardware ID
+        }
+        */
+        let expected = [
+            0x5b, 0x82, 0x0f, 0x50, 0x57, 0x52, 0x42, 0x08, 0x5f, 0x48, 0x49, 0x44, 0x0c, 0x41,
+            0xd0, 0x0c, 0x0c,
+        ];
+        let button = Button::power("PWRB".into());
+        let mut bytes = Vec::new();
+        button.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(button.aml_len(), bytes.len());
+    }
+
+    #[test]
+    fn test_battery_status() {
+        let battery = Battery::new("BAT0".into(), 0, 0xfed0_2000);
+        let mut bytes = Vec::new();
+        battery.bst().to_aml_bytes(&mut bytes);
+
+        let package = Path::new("BSTP");
+        let state = Path::new("BSTA");
+        let rate = Path::new("BRTE");
+        let capacity = Path::new("BRCP");
+        let voltage = Path::new("BVLT");
+        let mut expected = Vec::new();
+        Method::new(
+            "_BST".into(),
+            0,
+            true,
+            vec![
+                &Name::new(
+                    package.clone(),
+                    &Package::new(vec![&ZERO, &ZERO, &ZERO, &ZERO]),
+                ),
+                &Store::new(&Index::new(&ZERO, &package, &0usize), &state),
+                &Store::new(&Index::new(&ZERO, &package, &1usize), &rate),
+                &Store::new(&Index::new(&ZERO, &package, &2usize), &capacity),
+                &Store::new(&Index::new(&ZERO, &package, &3usize), &voltage),
+                &Return::new(&package),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_battery() {
+        let battery = Battery::new("_SB_.BAT0".into(), 1, 0xfed0_2000)
+            .capacity_levels(5000, 1000)
+            .model("VBAT")
+            .serial("0001")
+            .battery_type("LION")
+            .oem("rust-vmm");
+        let mut bytes = Vec::new();
+        battery.to_aml_bytes(&mut bytes);
+        assert_eq!(battery.aml_len(), bytes.len());
+
+        let bix = battery.bix();
+        assert_eq!(bix.elements.len(), 21);
+        assert!(matches!(bix.elements[8], Element::Register("BCYC")));
+        assert_eq!(battery.bif().elements.len(), 13);
+
+        let mut region = Vec::new();
+        OpRegion::new(
+            "BATR".into(),
+            OpRegionSpace::SystemMemory,
+            &0xfed0_2000u64,
+            &36u32,
+        )
+        .to_aml_bytes(&mut region);
+        let mut cycle_count = Vec::new();
+        register_field("BATR", *b"BCYC", 32).to_aml_bytes(&mut cycle_count);
+        let mut bix_bytes = Vec::new();
+        bix.to_aml_bytes(&mut bix_bytes);
+        for expected in [&region[..], &cycle_count, &bix_bytes, b"rust-vmm"] {
+            assert!(bytes.windows(expected.len()).any(|w| w == expected));
+        }
+    }
+
+    #[test]
+    fn test_ac_adapter_and_lid() {
+        let adapter = AcAdapter::new("_SB_.ADP0".into(), 0xfed0_3000);
+        let mut bytes = Vec::new();
+        adapter.to_aml_bytes(&mut bytes);
+        assert_eq!(adapter.aml_len(), bytes.len());
+
+        let scope = Path::new("\\_SB_");
+        let register = Path::new("ACST");
+        let mut expected = Vec::new();
+        Device::new(
+            "_SB_.ADP0".into(),
+            vec![
+                &Name::new("_HID".into(), &"ACPI0003"),
+                &Name::new("_PCL".into(), &Package::new(vec![&scope])),
+                &OpRegion::new(
+                    "ACR_".into(),
+                    OpRegionSpace::SystemMemory,
+                    &0xfed0_3000u64,
+                    &4u32,
+                ),
+                &register_field("ACR_", *b"ACST", 0),
+                &Method::new(
+                    "_PSR".into(),
+                    0,
+                    false,
+                    vec![&Return::new(&And::new(&Local(0), &register, &ONE))],
+                ),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+
+        let lid = Lid::new("_SB_.LID0".into(), 0xfed0_3004);
+        let mut bytes = Vec::new();
+        lid.to_aml_bytes(&mut bytes);
+        assert_eq!(lid.aml_len(), bytes.len());
+        assert!(bytes.windows(4).any(|w| w == b"_LID"));
+    }
+}
@@ -86,6 +86,10 @@ impl Aml for PPTT {
             st.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -180,6 +184,10 @@ impl Aml for ProcessorNode {
             sink.dword(r.0);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 #[derive(Default)]
@@ -374,6 +382,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         pptt.to_aml_bytes(&mut bytes);
+        assert_eq!(pptt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(size, bytes.len());
@@ -0,0 +1,191 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    aml::{Name, Package, PackageBuilder, Path},
+    Aml, AmlSink,
+};
+
+/// Interrupt that a PCI interrupt pin is routed to
+#[derive(Clone)]
+pub enum InterruptSource {
+    /// Global System Interrupt, wired directly to the interrupt
+    /// controller
+    Gsi(u32),
+    /// Interrupt link device (PNP0C0F) the pin is connected to
+    Link(Path),
+}
+
+/// Policy used to spread the interrupt pins of a range of slots over
+/// a set of interrupt sources
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Swizzle {
+    /// Pin `p` of slot `s` is routed to source `(s + p) % n`, as done
+    /// by PCI-to-PCI bridges
+    Standard,
+    /// Pin `p` of every slot is routed to source `p % n`
+    None,
+    /// All the pins of slot `s` are routed to source `s % n`
+    PerSlot,
+}
+
+impl Swizzle {
+    fn index(&self, slot: u8, pin: u8, sources: usize) -> usize {
+        let index = match self {
+            Swizzle::Standard => slot as usize + pin as usize,
+            Swizzle::None => pin as usize,
+            Swizzle::PerSlot => slot as usize,
+        };
+        index % sources
+    }
+}
+
+struct Route {
+    slot: u8,
+    pin: u8,
+    source: InterruptSource,
+}
+
+impl Aml for Route {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        // Any function of the device
+        let address = (self.slot as u32) << 16 | 0xffff;
+        let (source, source_index): (&dyn Aml, u32) = match &self.source {
+            InterruptSource::Gsi(gsi) => (&0u8, *gsi),
+            InterruptSource::Link(link) => (link, 0),
+        };
+        Package::new(vec![&address, &self.pin, source, &source_index]).to_aml_bytes(sink);
+    }
+}
+
+/// PCI Routing Table (`_PRT`) object of a PCI root bridge, mapping
+/// the interrupt pins (0 for INTA up to 3 for INTD) of the devices
+/// on the root bus to interrupts.
+#[derive(Default)]
+pub struct Prt {
+    routes: Vec<Route>,
+}
+
+impl Prt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_route(&mut self, slot: u8, pin: u8, source: InterruptSource) {
+        assert!(slot < 32);
+        assert!(pin < 4);
+        self.routes.push(Route { slot, pin, source });
+    }
+
+    /// Route the four interrupt pins of every slot in `slots` to
+    /// `sources`, following the `swizzle` policy.
+    pub fn add_slots(&mut self, slots: Range<u8>, swizzle: Swizzle, sources: &[InterruptSource]) {
+        assert!(!sources.is_empty());
+        for slot in slots {
+            for pin in 0..4 {
+                let source = &sources[swizzle.index(slot, pin, sources.len())];
+                self.add_route(slot, pin, source.clone());
+            }
+        }
+    }
+}
+
+impl Aml for Prt {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let mut package = PackageBuilder::new();
+        for route in &self.routes {
+            package.add_element(route);
+        }
+        Name::new("_PRT".into(), &package).to_aml_bytes(sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_devices() {
+        /*
+        Name (_PRT, Package (0x08)  // _PRT: PCI Routing Table
+        {
+            Package (0x04) { 0xFFFF, Zero, _SB.GSI0, Zero },
+            Package (0x04) { 0xFFFF, One, _SB.GSI1, Zero },
+            Package (0x04) { 0xFFFF, 0x02, _SB.GSI2, Zero },
+            Package (0x04) { 0xFFFF, 0x03, _SB.GSI3, Zero },
+            Package (0x04) { 0x0001FFFF, Zero, _SB.GSI1, Zero },
+            Package (0x04) { 0x0001FFFF, One, _SB.GSI2, Zero },
+            Package (0x04) { 0x0001FFFF, 0x02, _SB.GSI3, Zero },
+            Package (0x04) { 0x0001FFFF, 0x03, _SB.GSI0, Zero }
+        })
+        */
+        let expected = [
+            0x08, 0x5f, 0x50, 0x52, 0x54, 0x12, 0x47, 0x9, 0x8, 0x12, 0x10, 0x4, 0xb, 0xff, 0xff,
+            0x0, 0x2e, 0x5f, 0x53, 0x42, 0x5f, 0x47, 0x53, 0x49, 0x30, 0x0, 0x12, 0x10, 0x4, 0xb,
+            0xff, 0xff, 0x1, 0x2e, 0x5f, 0x53, 0x42, 0x5f, 0x47, 0x53, 0x49, 0x31, 0x0, 0x12, 0x11,
+            0x4, 0xb, 0xff, 0xff, 0xa, 0x2, 0x2e, 0x5f, 0x53, 0x42, 0x5f, 0x47, 0x53, 0x49, 0x32,
+            0x0, 0x12, 0x11, 0x4, 0xb, 0xff, 0xff, 0xa, 0x3, 0x2e, 0x5f, 0x53, 0x42, 0x5f, 0x47,
+            0x53, 0x49, 0x33, 0x0, 0x12, 0x12, 0x4, 0xc, 0xff, 0xff, 0x1, 0x0, 0x0, 0x2e, 0x5f,
+            0x53, 0x42, 0x5f, 0x47, 0x53, 0x49, 0x31, 0x0, 0x12, 0x12, 0x4, 0xc, 0xff, 0xff, 0x1,
+            0x0, 0x1, 0x2e, 0x5f, 0x53, 0x42, 0x5f, 0x47, 0x53, 0x49, 0x32, 0x0, 0x12, 0x13, 0x4,
+            0xc, 0xff, 0xff, 0x1, 0x0, 0xa, 0x2, 0x2e, 0x5f, 0x53, 0x42, 0x5f, 0x47, 0x53, 0x49,
+            0x33, 0x0, 0x12, 0x13, 0x4, 0xc, 0xff, 0xff, 0x1, 0x0, 0xa, 0x3, 0x2e, 0x5f, 0x53,
+            0x42, 0x5f, 0x47, 0x53, 0x49, 0x30, 0x0,
+        ];
+
+        let links: Vec<InterruptSource> = ["_SB_.GSI0", "_SB_.GSI1", "_SB_.GSI2", "_SB_.GSI3"]
+            .into_iter()
+            .map(|link| InterruptSource::Link(link.into()))
+            .collect();
+        let mut prt = Prt::new();
+        prt.add_slots(0..2, Swizzle::Standard, &links);
+
+        let mut bytes = Vec::new();
+        prt.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(prt.aml_len(), bytes.len());
+    }
+
+    #[test]
+    fn test_gsi() {
+        let mut prt = Prt::new();
+        prt.add_slots(1..3, Swizzle::PerSlot, &[32, 33].map(InterruptSource::Gsi));
+        prt.add_route(5, 3, InterruptSource::Gsi(40));
+
+        let mut bytes = Vec::new();
+        prt.to_aml_bytes(&mut bytes);
+        assert_eq!(prt.aml_len(), bytes.len());
+
+        let mut expected = Vec::new();
+        Name::new(
+            "_PRT".into(),
+            &Package::new(vec![
+                &Package::new(vec![&0x1ffffu32, &0u8, &0u8, &33u32]),
+                &Package::new(vec![&0x1ffffu32, &1u8, &0u8, &33u32]),
+                &Package::new(vec![&0x1ffffu32, &2u8, &0u8, &33u32]),
+                &Package::new(vec![&0x1ffffu32, &3u8, &0u8, &33u32]),
+                &Package::new(vec![&0x2ffffu32, &0u8, &0u8, &32u32]),
+                &Package::new(vec![&0x2ffffu32, &1u8, &0u8, &32u32]),
+                &Package::new(vec![&0x2ffffu32, &2u8, &0u8, &32u32]),
+                &Package::new(vec![&0x2ffffu32, &3u8, &0u8, &32u32]),
+                &Package::new(vec![&0x5ffffu32, &3u8, &0u8, &40u32]),
+            ]),
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_swizzle() {
+        assert_eq!(Swizzle::Standard.index(3, 2, 4), 1);
+        assert_eq!(Swizzle::None.index(3, 2, 4), 2);
+        assert_eq!(Swizzle::PerSlot.index(3, 2, 4), 3);
+        assert_eq!(Swizzle::None.index(0, 3, 2), 1);
+    }
+}
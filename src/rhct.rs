@@ -144,6 +144,10 @@ impl Aml for RHCT {
             st.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.table_header.length.get() as usize
+    }
 }
 
 #[repr(u16)]
@@ -197,6 +201,10 @@ impl Aml for IsaStringNode {
             sink.byte(0);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 // Each entry in the array contains the address offset of a RHCT node
@@ -250,6 +258,10 @@ impl Aml for HartInfoNode {
             sink.dword(*handle);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 pub struct CmoNode {
@@ -291,6 +303,10 @@ impl Aml for CmoNode {
         sink.byte(self.cbop_block_size);
         sink.byte(self.cboz_block_size);
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 #[repr(u8)]
@@ -329,6 +345,10 @@ impl Aml for MmuNode {
         sink.byte(0); // reserved
         sink.byte(self.supported_type);
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 #[cfg(test)]
@@ -346,6 +366,7 @@ mod tests {
         );
 
         rhct.to_aml_bytes(&mut bytes);
+        assert_eq!(rhct.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -365,6 +386,7 @@ mod tests {
         let _ = rhct.add_isa_string("quux");
 
         rhct.to_aml_bytes(&mut bytes);
+        assert_eq!(rhct.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -377,6 +399,7 @@ mod tests {
         rhct.add_mmu_node(VirtualAddressScheme::Sv57);
 
         rhct.to_aml_bytes(&mut bytes);
+        assert_eq!(rhct.aml_len(), bytes.len());
         assert_eq!(
             bytes[core::mem::size_of::<Header>() + MmuNode::len() - 1],
             VirtualAddressScheme::Sv57 as u8
@@ -409,6 +432,7 @@ mod tests {
         }
 
         rhct.to_aml_bytes(&mut bytes);
+        assert_eq!(rhct.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
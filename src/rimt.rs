@@ -109,6 +109,10 @@ impl Aml for RIMT {
             dev.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[repr(u8)]
@@ -189,6 +193,10 @@ impl Aml for InterruptWire {
         // APLIC ID
         sink.word(self.aplic_id);
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 /// This structure describes an IOMMU device
@@ -276,6 +284,10 @@ impl Aml for Iommu {
             }
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 pub struct IdMapping {
@@ -341,6 +353,10 @@ impl Aml for IdMapping {
         // Flags
         sink.dword(self.flags());
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 /// This structure describes a PCIe Root Complex device
@@ -420,6 +436,10 @@ impl Aml for PcieRootComplex {
             }
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 /// This structure describes a platform device
@@ -486,6 +506,10 @@ impl Aml for Platform {
             }
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 #[cfg(test)]
@@ -547,6 +571,7 @@ mod tests {
         let rimt = rimt();
         let mut bytes = Vec::new();
         rimt.to_aml_bytes(&mut bytes);
+        assert_eq!(rimt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), 48);
@@ -574,6 +599,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         rimt.to_aml_bytes(&mut bytes);
+        assert_eq!(rimt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), 168);
@@ -603,6 +629,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         rimt.to_aml_bytes(&mut bytes);
+        assert_eq!(rimt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), 184);
@@ -633,6 +660,7 @@ mod tests {
         assert_eq!(int_wire.flags(), 3);
         let mut bytes = Vec::new();
         int_wire.to_aml_bytes(&mut bytes);
+        assert_eq!(int_wire.aml_len(), bytes.len());
         assert_eq!(bytes.len(), 8);
         assert_eq!(bytes, vec![1, 0, 0, 0, 3, 0, 1, 0]);
     }
@@ -659,6 +687,7 @@ mod tests {
         assert_eq!(iommu.flags(), 3);
         let mut bytes = Vec::new();
         iommu.to_aml_bytes(&mut bytes);
+        assert_eq!(iommu.aml_len(), bytes.len());
         assert_eq!(bytes.len(), 32);
         assert_eq!(
             bytes,
@@ -689,6 +718,7 @@ mod tests {
         assert_eq!(id_mapping.flags(), 7);
         let mut bytes = Vec::new();
         id_mapping.to_aml_bytes(&mut bytes);
+        assert_eq!(id_mapping.aml_len(), bytes.len());
         assert_eq!(bytes.len(), 20);
         assert_eq!(
             bytes,
@@ -712,6 +742,7 @@ mod tests {
         assert_eq!(pcierc.flags(), 3);
         let mut bytes = Vec::new();
         pcierc.to_aml_bytes(&mut bytes);
+        assert_eq!(pcierc.aml_len(), bytes.len());
         assert_eq!(bytes.len(), 56);
         assert_eq!(
             bytes,
@@ -739,6 +770,7 @@ mod tests {
         assert_eq!(platform.len(), 69);
         let mut bytes = Vec::new();
         platform.to_aml_bytes(&mut bytes);
+        assert_eq!(platform.aml_len(), bytes.len());
         assert_eq!(bytes.len(), 69);
         assert_eq!(
             bytes,
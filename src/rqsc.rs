@@ -42,7 +42,8 @@ impl RQSC {
 
         let mut header = TableHeader {
             signature: *b"RQSC",
-            length: (TableHeader::len() as u32).into(),
+            // Number of QoS controllers follows the header
+            length: (TableHeader::len() as u32 + 4).into(),
             revision: 1,
             checksum: 0,
             oem_id,
@@ -90,6 +91,10 @@ impl Aml for RQSC {
             st.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -173,6 +178,10 @@ impl Aml for QoSController {
             .iter()
             .for_each(|resource| resource.to_aml_bytes(sink));
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 /// Resource Structures asssociated with a specific QoS controller
@@ -226,6 +235,10 @@ impl Aml for ResourceStructure {
         sink.byte(0);
         self.resource_id.to_aml_bytes(sink)
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 /// Regroups Resource ID Type, Resource ID 1, Resource ID 2 and Resource
@@ -283,6 +296,10 @@ impl Aml for ResourceID {
             ResourceID::VendorSpecific(_, resource) => sink.vec(resource.as_bytes()),
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 #[derive(Clone, Debug, Default, IntoBytes, Immutable)]
@@ -404,6 +421,7 @@ mod tests {
         let rqsc = RQSC::new(*b"RQSSCC", *b"SOMETHIN", 0xcafe_d00d);
         let mut bytes = Vec::new();
         rqsc.to_aml_bytes(&mut bytes);
+        assert_eq!(rqsc.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), TableHeader::len() + 4);
@@ -431,6 +449,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         rqsc.to_aml_bytes(&mut bytes);
+        assert_eq!(rqsc.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
 
         assert_eq!(sum, 0);
@@ -481,6 +500,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         rqsc.to_aml_bytes(&mut bytes);
+        assert_eq!(rqsc.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
 
         assert_eq!(sum, 0);
@@ -158,6 +158,10 @@ impl Aml for Sdt {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
         sink.vec(&self.data);
     }
+
+    fn aml_len(&self) -> usize {
+        self.len()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,346 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+extern crate alloc;
+use alloc::{format, vec, vec::Vec};
+
+use crate::{
+    aml::{
+        Arg, Field, FieldAccessType, FieldEntry, FieldLockRule, FieldUpdateRule, Method, Name,
+        OpRegion, OpRegionSpace, Package, Path, Return, Store, ZERO,
+    },
+    fadt::FADTBuilder,
+    gas::{AccessSize, AddressSpace, GAS},
+    Aml, AmlSink,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum SleepState {
+    S0 = 0,
+    S1 = 1,
+    S2 = 2,
+    S3 = 3,
+    S4 = 4,
+    S5 = 5,
+}
+
+/// Register the OS writes the SLP_TYP value of a sleep state to in
+/// order to enter it
+#[derive(Copy, Clone, Debug)]
+pub enum SleepControl {
+    /// 16-bit PM1a control register at the given port, with SLP_TYP in
+    /// bits 10 to 12 and SLP_EN in bit 13
+    Pm1Control(u16),
+    /// 8-bit sleep control and status registers of hardware-reduced
+    /// platforms. SLP_TYP is in bits 2 to 4 of the control register,
+    /// and SLP_EN in bit 5.
+    SleepRegisters { control: GAS, status: GAS },
+}
+
+impl SleepControl {
+    // Offsets of SLP_TYP and SLP_EN in the control register
+    fn layout(&self) -> (u16, u16) {
+        match self {
+            SleepControl::Pm1Control(_) => (10, 13),
+            SleepControl::SleepRegisters { .. } => (2, 5),
+        }
+    }
+}
+
+struct SupportedState {
+    state: SleepState,
+    slp_typ: u8,
+}
+
+impl Aml for SupportedState {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        let path = Path::new(&format!("_S{}_", self.state as u8));
+        // The same value is used for PM1a and PM1b, the other two
+        // elements being reserved
+        Name::new(
+            path,
+            &Package::new(vec![&self.slp_typ, &self.slp_typ, &ZERO, &ZERO]),
+        )
+        .to_aml_bytes(sink);
+    }
+}
+
+/// Sleep states supported by the platform, generating the `_Sx_`
+/// objects giving their SLP_TYP values along with the `_PTS` and
+/// `_WAK` methods. The FADT registers through which the OS requests
+/// a sleep state are configured from the same description, see
+/// [`SleepStates::setup_fadt`], and [`SleepStates::decode`] gives the
+/// state requested by a value written to the control register.
+pub struct SleepStates {
+    control: SleepControl,
+    states: Vec<SupportedState>,
+    pts_register: Option<(OpRegionSpace, u64)>,
+}
+
+impl SleepStates {
+    pub fn new(control: SleepControl) -> Self {
+        SleepStates {
+            control,
+            states: Vec::new(),
+            pts_register: None,
+        }
+    }
+
+    /// Support `state`, requested with `slp_typ`. Apart from S0, each
+    /// state must have its own SLP_TYP value so that it can be decoded.
+    pub fn add_state(&mut self, state: SleepState, slp_typ: u8) {
+        assert!(slp_typ < 8);
+        assert!(self.slp_typ(state).is_none());
+        if state != SleepState::S0 {
+            assert!(self
+                .states
+                .iter()
+                .all(|other| other.state == SleepState::S0 || other.slp_typ != slp_typ));
+        }
+        self.states.push(SupportedState { state, slp_typ });
+    }
+
+    /// Write the sleep state about to be entered to the byte register
+    /// at `address` from `_PTS`, and 0 from `_WAK` once woken up.
+    pub fn pts_register(mut self, space: OpRegionSpace, address: u64) -> Self {
+        self.pts_register = Some((space, address));
+        self
+    }
+
+    /// SLP_TYP value of `state`, if supported
+    pub fn slp_typ(&self, state: SleepState) -> Option<u8> {
+        self.states
+            .iter()
+            .find(|supported| supported.state == state)
+            .map(|supported| supported.slp_typ)
+    }
+
+    /// Sleep state requested by writing `value` to the control
+    /// register, if SLP_EN is set along with the SLP_TYP value of a
+    /// supported state. S0 is never requested this way, so that it may
+    /// share its SLP_TYP value with another state.
+    pub fn decode(&self, value: u16) -> Option<SleepState> {
+        let (slp_typ_shift, slp_en_shift) = self.control.layout();
+        if value & (1 << slp_en_shift) == 0 {
+            return None;
+        }
+        let slp_typ = ((value >> slp_typ_shift) & 0x7) as u8;
+        self.states
+            .iter()
+            .filter(|supported| supported.state != SleepState::S0)
+            .find(|supported| supported.slp_typ == slp_typ)
+            .map(|supported| supported.state)
+    }
+
+    /// Point the FADT to the control register of the sleep states.
+    pub fn setup_fadt(&self, mut fadt: FADTBuilder) -> FADTBuilder {
+        match self.control {
+            SleepControl::Pm1Control(port) => {
+                fadt.pm1a_cnt_blk = (port as u32).into();
+                fadt.pm1_cnt_len = 2;
+                fadt.x_pm1a_cnt_blk = GAS::new(
+                    AddressSpace::SystemIo,
+                    16,
+                    0,
+                    AccessSize::WordAccess,
+                    port.into(),
+                );
+            }
+            SleepControl::SleepRegisters { control, status } => {
+                fadt.sleep_control_reg = control;
+                fadt.sleep_status_reg = status;
+            }
+        }
+        fadt
+    }
+}
+
+impl Aml for SleepStates {
+    fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
+        for state in &self.states {
+            state.to_aml_bytes(sink);
+        }
+
+        // Status and power supply state of _WAK, both left to 0
+        let wake_status = Package::new(vec![&ZERO, &ZERO]);
+        let wake_return = Return::new(&wake_status);
+        match self.pts_register {
+            Some((space, address)) => {
+                let state = Path::new("SLPS");
+                OpRegion::new("SLPR".into(), space, &address, &1u8).to_aml_bytes(sink);
+                Field::new(
+                    "SLPR".into(),
+                    FieldAccessType::Byte,
+                    FieldLockRule::NoLock,
+                    FieldUpdateRule::Preserve,
+                    vec![FieldEntry::Named(*b"SLPS", 8)],
+                )
+                .to_aml_bytes(sink);
+                Method::new("_PTS".into(), 1, false, vec![&Store::new(&state, &Arg(0))])
+                    .to_aml_bytes(sink);
+                Method::new(
+                    "_WAK".into(),
+                    1,
+                    false,
+                    vec![&Store::new(&state, &ZERO), &wake_return],
+                )
+                .to_aml_bytes(sink);
+            }
+            None => {
+                Method::new("_PTS".into(), 1, false, vec![]).to_aml_bytes(sink);
+                Method::new("_WAK".into(), 1, false, vec![&wake_return]).to_aml_bytes(sink);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleep_state_package() {
+        /*
+        Name (_S5, Package (0x04)  // _S5_: S5 System State
+        {
+            0x05,
+            0x05,
+            Zero,
+            Zero
+        })
+        */
+        let expected = [
+            0x08, 0x5f, 0x53, 0x35, 0x5f, 0x12, 0x08, 0x04, 0x0a, 0x05, 0x0a, 0x05, 0x00, 0x00,
+        ];
+        let state = SupportedState {
+            state: SleepState::S5,
+            slp_typ: 5,
+        };
+        let mut bytes = Vec::new();
+        state.to_aml_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(state.aml_len(), bytes.len());
+    }
+
+    #[test]
+    fn test_pm1_control() {
+        let mut sleep = SleepStates::new(SleepControl::Pm1Control(0x604))
+            .pts_register(OpRegionSpace::SystemIO, 0x610);
+        sleep.add_state(SleepState::S0, 0);
+        sleep.add_state(SleepState::S3, 1);
+        sleep.add_state(SleepState::S5, 0);
+
+        let mut bytes = Vec::new();
+        sleep.to_aml_bytes(&mut bytes);
+        assert_eq!(sleep.aml_len(), bytes.len());
+
+        let state = Path::new("SLPS");
+        let mut expected = Vec::new();
+        Name::new("_S0_".into(), &Package::new(vec![&0u8, &0u8, &ZERO, &ZERO]))
+            .to_aml_bytes(&mut expected);
+        Name::new("_S3_".into(), &Package::new(vec![&1u8, &1u8, &ZERO, &ZERO]))
+            .to_aml_bytes(&mut expected);
+        Name::new("_S5_".into(), &Package::new(vec![&0u8, &0u8, &ZERO, &ZERO]))
+            .to_aml_bytes(&mut expected);
+        OpRegion::new("SLPR".into(), OpRegionSpace::SystemIO, &0x610u64, &1u8)
+            .to_aml_bytes(&mut expected);
+        Field::new(
+            "SLPR".into(),
+            FieldAccessType::Byte,
+            FieldLockRule::NoLock,
+            FieldUpdateRule::Preserve,
+            vec![FieldEntry::Named(*b"SLPS", 8)],
+        )
+        .to_aml_bytes(&mut expected);
+        Method::new("_PTS".into(), 1, false, vec![&Store::new(&state, &Arg(0))])
+            .to_aml_bytes(&mut expected);
+        Method::new(
+            "_WAK".into(),
+            1,
+            false,
+            vec![
+                &Store::new(&state, &ZERO),
+                &Return::new(&Package::new(vec![&ZERO, &ZERO])),
+            ],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+
+        assert_eq!(sleep.slp_typ(SleepState::S3), Some(1));
+        assert_eq!(sleep.slp_typ(SleepState::S4), None);
+        // SLP_EN with SLP_TYP 1
+        assert_eq!(sleep.decode(0x2400), Some(SleepState::S3));
+        assert_eq!(sleep.decode(0x0400), None);
+        assert_eq!(sleep.decode(0x2800), None);
+        assert_eq!(sleep.decode(0x2000), Some(SleepState::S5));
+
+        let fadt = sleep.setup_fadt(FADTBuilder::new(*b"TEST__", *b"TESTTEST", 1));
+        assert_eq!(u32::from(fadt.pm1a_cnt_blk), 0x604);
+        assert_eq!(fadt.pm1_cnt_len, 2);
+        assert_eq!(u64::from(fadt.x_pm1a_cnt_blk.address), 0x604);
+    }
+
+    #[test]
+    fn test_sleep_registers() {
+        let control = GAS::new(
+            AddressSpace::SystemMemory,
+            8,
+            0,
+            AccessSize::ByteAccess,
+            0xfed0_1000,
+        );
+        let status = GAS::new(
+            AddressSpace::SystemMemory,
+            8,
+            0,
+            AccessSize::ByteAccess,
+            0xfed0_1001,
+        );
+        let mut sleep = SleepStates::new(SleepControl::SleepRegisters { control, status });
+        sleep.add_state(SleepState::S5, 5);
+
+        let mut bytes = Vec::new();
+        sleep.to_aml_bytes(&mut bytes);
+        assert_eq!(sleep.aml_len(), bytes.len());
+
+        let mut expected = Vec::new();
+        Name::new("_S5_".into(), &Package::new(vec![&5u8, &5u8, &ZERO, &ZERO]))
+            .to_aml_bytes(&mut expected);
+        Method::new("_PTS".into(), 1, false, vec![]).to_aml_bytes(&mut expected);
+        Method::new(
+            "_WAK".into(),
+            1,
+            false,
+            vec![&Return::new(&Package::new(vec![&ZERO, &ZERO]))],
+        )
+        .to_aml_bytes(&mut expected);
+        assert_eq!(bytes, expected);
+
+        // SLP_EN with SLP_TYP 5
+        assert_eq!(sleep.decode(0x34), Some(SleepState::S5));
+
+        let fadt = sleep.setup_fadt(FADTBuilder::new(*b"TEST__", *b"TESTTEST", 1));
+        assert_eq!(u64::from(fadt.sleep_control_reg.address), 0xfed0_1000);
+        assert_eq!(u64::from(fadt.sleep_status_reg.address), 0xfed0_1001);
+        assert_eq!(u32::from(fadt.pm1a_cnt_blk), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_state() {
+        let mut sleep = SleepStates::new(SleepControl::Pm1Control(0x604));
+        sleep.add_state(SleepState::S3, 1);
+        sleep.add_state(SleepState::S3, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_slp_typ() {
+        let mut sleep = SleepStates::new(SleepControl::Pm1Control(0x604));
+        sleep.add_state(SleepState::S3, 1);
+        sleep.add_state(SleepState::S4, 1);
+    }
+}
@@ -86,6 +86,10 @@ impl Aml for SLIT {
             sink.byte(*entry);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[cfg(test)]
@@ -103,6 +107,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         slit.to_aml_bytes(&mut bytes);
+        assert_eq!(slit.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -118,6 +123,7 @@ mod tests {
         let mut bytes = Vec::new();
         slit.set_distance(0, 1, 15);
         slit.to_aml_bytes(&mut bytes);
+        assert_eq!(slit.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -139,6 +145,7 @@ mod tests {
         slit.set_distance(2, 3, 35);
 
         slit.to_aml_bytes(&mut bytes);
+        assert_eq!(slit.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
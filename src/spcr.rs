@@ -11,25 +11,79 @@ use zerocopy::{
 use crate::{assert_same_size, gas, Aml, AmlSink, Checksum, TableHeader};
 use core::mem::size_of;
 
+extern crate alloc;
+use alloc::string::String;
+
 type U16 = byteorder::U16<LE>;
 type U32 = byteorder::U32<LE>;
 
 const PCI_VENDOR_ID_NONE: u16 = 0xffff;
 const PCI_DEVICE_ID_NONE: u16 = 0xffff;
 
-const EMPTY_NAMESPACE: [u8; 2] = [b'.', 0];
+// Path of the console when it is not described in the namespace
+const EMPTY_NAMESPACE: &str = ".";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum InterruptType {
+    IoApic = 1 << 1,
+    IoSapic = 1 << 2,
+    ArmGic = 1 << 3,
+    RiscvPlic = 1 << 4,
+}
+
+// The PC-AT compatible IRQ has its own field
+const INTERRUPT_TYPE_PC_AT: u8 = 1 << 0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum BaudRate {
+    /// The rate already configured by the firmware
+    #[default]
+    AsIs = 0,
+    B9600 = 3,
+    B19200 = 4,
+    B57600 = 6,
+    B115200 = 7,
+}
 
-pub struct SPCR<'a> {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FlowControl {
+    Dcd = 1 << 0,
+    RtsCts = 1 << 1,
+    XonXoff = 1 << 2,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum TerminalType {
+    #[default]
+    Vt100 = 0,
+    ExtendedVt100 = 1,
+    VtUtf8 = 2,
+    Ansi = 3,
+}
+
+/// The Serial Port Console Redirection table describes the serial
+/// port used as a console, following revision 4 of the table.
+pub struct SPCR {
     header: TableHeader,
     info: SerialPortInfo,
-    namespace_string: &'a [u8],
+    namespace_string: String,
 }
 
-impl SPCR<'_> {
-    pub fn sbi(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
-        let mut header = TableHeader {
+impl SPCR {
+    pub fn new(
+        oem_id: [u8; 6],
+        oem_table_id: [u8; 8],
+        oem_revision: u32,
+        interface_type: SerialPortSubType,
+        base_address: gas::GAS,
+    ) -> Self {
+        let header = TableHeader {
             signature: *b"SPCR",
-            length: (TableHeader::len() as u32).into(),
+            length: 0.into(),
             revision: 4,
             checksum: 0,
             oem_id,
@@ -38,42 +92,144 @@ impl SPCR<'_> {
             creator_id: crate::CREATOR_ID,
             creator_revision: crate::CREATOR_REVISION,
         };
-        let sbi = SerialPortInfo::sbi();
-
-        let mut cksum = Checksum::default();
-        cksum.append(header.as_bytes());
-        cksum.append(sbi.as_bytes());
-        cksum.append(&EMPTY_NAMESPACE);
-        header.checksum = cksum.value();
 
         Self {
             header,
-            info: sbi,
-            namespace_string: &EMPTY_NAMESPACE,
+            info: SerialPortInfo::new(interface_type, base_address),
+            namespace_string: EMPTY_NAMESPACE.into(),
         }
+        .update_header()
+    }
+
+    pub fn sbi(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
+        Self::new(
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            SerialPortSubType::RiscvSbi,
+            gas::GAS::default(),
+        )
+    }
+
+    fn update_header(mut self) -> Self {
+        // Null terminated
+        let namespace_len = self.namespace_string.len() + 1;
+        self.info.namespace_string_len = (namespace_len as u16).into();
+        self.header.length = (self.aml_len() as u32).into();
+
+        let mut cksum = Checksum::default();
+        self.header.checksum = 0;
+        cksum.append(self.header.as_bytes());
+        cksum.append(self.info.as_bytes());
+        cksum.append(self.namespace_string.as_bytes());
+        self.header.checksum = cksum.value();
+        self
+    }
+
+    /// Interrupt of a PC-AT compatible 8259 interrupt controller
+    pub fn irq(mut self, irq: u8) -> Self {
+        self.info.interrupt_type |= INTERRUPT_TYPE_PC_AT;
+        self.info.irq = irq;
+        self.update_header()
+    }
+
+    pub fn gsi(mut self, interrupt_type: InterruptType, gsi: u32) -> Self {
+        self.info.interrupt_type |= interrupt_type as u8;
+        self.info.gsi = gsi.into();
+        self.update_header()
+    }
+
+    pub fn baud_rate(mut self, baud_rate: BaudRate) -> Self {
+        self.info.baud_rate = baud_rate as u8;
+        self.update_header()
+    }
+
+    pub fn flow_control(mut self, flow_control: FlowControl) -> Self {
+        self.info.flow_control |= flow_control as u8;
+        self.update_header()
+    }
+
+    pub fn terminal_type(mut self, terminal_type: TerminalType) -> Self {
+        self.info.terminal_type = terminal_type as u8;
+        self.update_header()
+    }
+
+    /// PCI function of the UART, identified by its IDs and location
+    pub fn pci(
+        mut self,
+        vendor_id: u16,
+        device_id: u16,
+        segment: u8,
+        bus: u8,
+        device: u8,
+        function: u8,
+    ) -> Self {
+        assert!(device < 32);
+        assert!(function < 8);
+
+        self.info.pci_vendor_id = vendor_id.into();
+        self.info.pci_device_id = device_id.into();
+        self.info.pci_segment = segment;
+        self.info.pci_bus = bus;
+        self.info.pci_device = device;
+        self.info.pci_function = function;
+        self.update_header()
+    }
+
+    /// The OS must not suppress the PnP enumeration of the PCI UART,
+    /// or disable its power management
+    pub fn pci_keep_enumeration(mut self) -> Self {
+        self.info.pci_flags = (self.info.pci_flags.get() | 1).into();
+        self.update_header()
+    }
+
+    /// Frequency of the clock of the UART, in Hz
+    pub fn clock_frequency(mut self, frequency: u32) -> Self {
+        self.info.clock_frequency = frequency.into();
+        self.update_header()
+    }
+
+    /// Baud rate not expressible by [`BaudRate`], overriding it
+    pub fn precise_baud_rate(mut self, baud_rate: u32) -> Self {
+        self.info.precise_baud = baud_rate.into();
+        self.update_header()
+    }
+
+    /// Full path of the console in the namespace, e.g. `\_SB.COM0`
+    pub fn namespace(mut self, namespace: String) -> Self {
+        assert!(!namespace.is_empty());
+        self.namespace_string = namespace;
+        self.update_header()
     }
 }
 
-impl Aml for SPCR<'_> {
+impl Aml for SPCR {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
         sink.vec(self.header.as_bytes());
         sink.vec(self.info.as_bytes());
-        sink.vec(self.namespace_string);
+        sink.vec(self.namespace_string.as_bytes());
+        sink.byte(0);
+    }
+
+    fn aml_len(&self) -> usize {
+        TableHeader::len() + SerialPortInfo::len() + self.namespace_string.len() + 1
     }
 }
 
-#[allow(dead_code)]
+// Port types shared with the DBG2 table
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u16)]
-enum SerialPortType {
+pub(crate) enum SerialPortType {
     Serial = 0x8000,
     Uart1394 = 0x8001,
     Usb = 0x8002,
     Net = 0x8003,
 }
 
-#[allow(dead_code)]
+/// Interface type of the serial port
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u8)]
-enum SerialPortSubType {
+pub enum SerialPortSubType {
     Fully16550Compatible = 0,
     Subset16550 = 1,
     Max311xESpi = 2,
@@ -133,31 +289,17 @@ impl SerialPortInfo {
         size_of::<Self>()
     }
 
-    pub fn sbi() -> Self {
+    fn new(interface_type: SerialPortSubType, base_address: gas::GAS) -> Self {
         Self {
-            interface_type: SerialPortSubType::RiscvSbi as u8,
-            reserved0: [0, 0, 0],
-            base_address: gas::GAS::default(),
-            interrupt_type: 0,
-            irq: 0,
-            gsi: 0.into(),
-            baud_rate: 0,
-            parity: 0,
-            stop_bits: 0,
-            flow_control: 0,
-            terminal_type: 0,
-            language: 0,
+            interface_type: interface_type as u8,
+            base_address,
+            stop_bits: 1,
             pci_device_id: PCI_DEVICE_ID_NONE.into(),
             pci_vendor_id: PCI_VENDOR_ID_NONE.into(),
-            pci_bus: 0,
-            pci_device: 0,
-            pci_function: 0,
-            pci_flags: 0.into(),
-            pci_segment: 0,
-            clock_frequency: 0.into(),
-            precise_baud: 0.into(),
-            namespace_string_len: 2.into(),
-            namespace_string_offset: (Self::len() as u16).into(),
+            // The namespace string directly follows the structure, the
+            // offset being from the start of the table
+            namespace_string_offset: ((TableHeader::len() + Self::len()) as u16).into(),
+            ..Default::default()
         }
     }
 }
@@ -165,17 +307,80 @@ impl SerialPortInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloc::vec::Vec;
+    use crate::test_utils::check_table;
 
     #[test]
     fn test_sbi_spcr() {
         let spcr = SPCR::sbi(*b"SSPCRR", *b"SOMETHIN", 0xcafe_d00d);
-        let mut bytes = Vec::new();
-        spcr.to_aml_bytes(&mut bytes);
-
-        let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
-        assert_eq!(sum, 0);
+        let bytes = check_table(&spcr);
         assert_eq!(bytes.len(), TableHeader::len() + SerialPortInfo::len() + 2);
         assert_eq!(bytes[0..4], *b"SPCR");
+        assert_eq!(bytes[4..8], (bytes.len() as u32).to_le_bytes());
+        assert_eq!(bytes[84..], [2, 0, 88, 0, b'.', 0]);
+    }
+
+    #[test]
+    fn test_16550_spcr() {
+        let spcr = SPCR::new(
+            *b"SSPCRR",
+            *b"SOMETHIN",
+            0xcafe_d00d,
+            SerialPortSubType::Fully16550Compatible,
+            gas::GAS::new(
+                gas::AddressSpace::SystemIo,
+                8,
+                0,
+                gas::AccessSize::ByteAccess,
+                0x3f8,
+            ),
+        )
+        .irq(4)
+        .baud_rate(BaudRate::B115200)
+        .flow_control(FlowControl::RtsCts)
+        .terminal_type(TerminalType::VtUtf8);
+        let bytes = check_table(&spcr);
+
+        assert_eq!(
+            bytes[36..],
+            [
+                0, 0, 0, 0, // interface type
+                1, 8, 0, 1, 0xf8, 0x03, 0, 0, 0, 0, 0, 0, // base address
+                1, 4, 0, 0, 0, 0, // interrupts
+                7, 0, 1, 2, 2, 0, // baud rate, parity, stop bits, flow control, terminal
+                0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, // no PCI
+                0, 0, 0, 0, 0, 0, 0, 0, // clock and precise baud rate
+                2, 0, 88, 0, b'.', 0
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pl011_spcr() {
+        let spcr = SPCR::new(
+            *b"SSPCRR",
+            *b"SOMETHIN",
+            0xcafe_d00d,
+            SerialPortSubType::ArmPl011,
+            gas::GAS::new(
+                gas::AddressSpace::SystemMemory,
+                32,
+                0,
+                gas::AccessSize::DwordAccess,
+                0x900_0000,
+            ),
+        )
+        .gsi(InterruptType::ArmGic, 33)
+        .clock_frequency(24_000_000)
+        .precise_baud_rate(1_500_000)
+        .namespace("\\_SB.COM0".into());
+        let bytes = check_table(&spcr);
+        assert_eq!(bytes[4..8], (bytes.len() as u32).to_le_bytes());
+
+        assert_eq!(bytes[36], 3);
+        assert_eq!(bytes[52..58], [8, 0, 33, 0, 0, 0]);
+        assert_eq!(bytes[76..80], 24_000_000u32.to_le_bytes());
+        assert_eq!(bytes[80..84], 1_500_000u32.to_le_bytes());
+        assert_eq!(bytes[84..88], [10, 0, 88, 0]);
+        assert_eq!(bytes[88..], *b"\\_SB.COM0\0");
     }
 }
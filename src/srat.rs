@@ -93,6 +93,10 @@ impl Aml for SRAT {
             st.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[repr(u8)]
@@ -164,6 +168,10 @@ impl Aml for MemoryAffinity {
         sink.dword(self.flags);
         sink.qword(0); // reserved
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -284,6 +292,10 @@ impl Aml for GenericInitiator {
         sink.dword(self.flags);
         sink.dword(0); // reserved
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 #[repr(C, packed)]
@@ -337,6 +349,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         srat.to_aml_bytes(&mut bytes);
+        assert_eq!(srat.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -354,6 +367,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         srat.to_aml_bytes(&mut bytes);
+        assert_eq!(srat.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -388,6 +402,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         srat.to_aml_bytes(&mut bytes);
+        assert_eq!(srat.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -399,6 +414,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         srat.to_aml_bytes(&mut bytes);
+        assert_eq!(srat.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -71,6 +71,10 @@ impl Aml for TpmClient1_2 {
         sink.dword(self.log_area_min_len);
         sink.qword(self.log_area_start_addr);
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[derive(Copy, Clone, Default, IntoBytes, Immutable)]
@@ -197,6 +201,10 @@ impl Aml for TpmServer1_2 {
     fn to_aml_bytes(&self, sink: &mut dyn AmlSink) {
         sink.vec(self.as_bytes());
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 /// TPM 2.0
@@ -300,6 +308,10 @@ impl Aml for Tpm2 {
             sink.qword(lasa);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[cfg(test)]
@@ -320,6 +332,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         client.to_aml_bytes(&mut bytes);
+        assert_eq!(client.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -350,6 +363,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         server.to_aml_bytes(&mut bytes);
+        assert_eq!(server.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), 100);
@@ -367,6 +381,7 @@ mod tests {
         );
         let mut bytes = Vec::new();
         tpm2.to_aml_bytes(&mut bytes);
+        assert_eq!(tpm2.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), 52);
@@ -375,6 +390,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         tpm2.to_aml_bytes(&mut bytes);
+        assert_eq!(tpm2.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), 76);
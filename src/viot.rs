@@ -112,6 +112,10 @@ impl Aml for VIOT {
             st.to_aml_bytes(sink);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[repr(u8)]
@@ -126,7 +130,7 @@ enum ViotEntryType {
 pub struct TranslationHandle(u16);
 
 pub struct PciDevice {
-    segment: u16,
+    pub(crate) segment: u16,
     bus: u8,
     device: u8,
     function: u8,
@@ -145,7 +149,7 @@ impl PciDevice {
         }
     }
 
-    fn as_bdf(&self) -> u16 {
+    pub(crate) fn as_bdf(&self) -> u16 {
         ((self.bus as u16) << 8) | ((self.device as u16) << 3) | self.function as u16
     }
 }
@@ -190,6 +194,10 @@ impl Aml for PciRange {
         sink.word(0);
         sink.dword(0);
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 /// A single endpoint identified by its base MMIO address.
@@ -229,6 +237,10 @@ impl Aml for MmioEndpoint {
         sink.word(0);
         sink.dword(0);
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 /// A virtio-iommu device (possibly based on virtio-pci transport)
@@ -259,6 +271,10 @@ impl Aml for VirtIoPciIommu {
         sink.word(self.device.as_bdf());
         sink.qword(0); // reserved
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 /// A virtio-iommu device based on virtio-mmio transport
@@ -288,6 +304,10 @@ impl Aml for VirtIoMmioIommu {
         sink.dword(0); // reserved
         sink.qword(self.base_addr);
     }
+
+    fn aml_len(&self) -> usize {
+        Self::len()
+    }
 }
 
 #[cfg(test)]
@@ -300,6 +320,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         viot.to_aml_bytes(&mut bytes);
+        assert_eq!(viot.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -320,6 +341,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         viot.to_aml_bytes(&mut bytes);
+        assert_eq!(viot.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), len);
@@ -341,6 +363,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         viot.to_aml_bytes(&mut bytes);
+        assert_eq!(viot.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), len);
@@ -362,6 +385,7 @@ mod tests {
 
         let mut bytes = Vec::new();
         viot.to_aml_bytes(&mut bytes);
+        assert_eq!(viot.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
         assert_eq!(bytes.len(), len);
@@ -0,0 +1,279 @@
+// Copyright 2026 The rust-vmm Authors
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+
+use zerocopy::{
+    byteorder::{self, LE},
+    Immutable, IntoBytes,
+};
+
+use crate::{Aml, AmlSink, TableHeader};
+
+type U16 = byteorder::U16<LE>;
+type U32 = byteorder::U32<LE>;
+type U64 = byteorder::U64<LE>;
+
+fn header(
+    signature: [u8; 4],
+    len: usize,
+    oem_id: [u8; 6],
+    oem_table_id: [u8; 8],
+    oem_revision: u32,
+) -> TableHeader {
+    TableHeader {
+        signature,
+        length: (len as u32).into(),
+        revision: 1,
+        checksum: 0,
+        oem_id,
+        oem_table_id,
+        oem_revision: oem_revision.into(),
+        creator_id: crate::CREATOR_ID,
+        creator_revision: crate::CREATOR_REVISION,
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum WaetFlags {
+    /// The RTC does not need the status register C to be read after
+    /// an interrupt
+    RtcGood = 1 << 0,
+    /// The PM timer can be read once, without checking for an
+    /// overflow
+    PmTimerGood = 1 << 1,
+}
+
+/// The Windows ACPI Emulated devices Table tells the guest which of
+/// its emulated devices can be accessed without the workarounds
+/// needed on real hardware, saving VM exits.
+#[derive(Copy, Clone, Default, IntoBytes, Immutable)]
+#[repr(C, packed)]
+pub struct WAET {
+    header: TableHeader,
+    emulated_device_flags: U32,
+}
+
+crate::assert_same_size!(WAET, [u8; 40]);
+
+impl WAET {
+    pub fn new(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
+        Self {
+            header: header(*b"WAET", Self::len(), oem_id, oem_table_id, oem_revision),
+            ..Default::default()
+        }
+        .update_header()
+    }
+
+    fn update_header(mut self) -> Self {
+        self.header.checksum = 0;
+        self.header.checksum = crate::generate_checksum(self.as_bytes());
+        self
+    }
+
+    pub fn flag(mut self, flag: WaetFlags) -> Self {
+        self.emulated_device_flags = (self.emulated_device_flags.get() | flag as u32).into();
+        self.update_header()
+    }
+
+    pub fn len() -> usize {
+        core::mem::size_of::<Self>()
+    }
+}
+
+crate::aml_as_bytes!(WAET);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum WsmtFlags {
+    /// The SMI handlers only use communication buffers that are
+    /// fixed and reported by the firmware
+    FixedCommBuffers = 1 << 0,
+    /// The pointers found in the communication buffers are validated
+    /// as well. This requires `FixedCommBuffers`, which is set along
+    /// with it.
+    CommBufferNestedPtrProtection = 1 << 1,
+    /// The SMI handlers do not let OSPM change the configuration of
+    /// the critical system resources
+    SystemResourceProtection = 1 << 2,
+}
+
+/// The Windows SMM Security Mitigations Table reports the protections
+/// implemented by the SMI handlers of the firmware.
+#[derive(Copy, Clone, Default, IntoBytes, Immutable)]
+#[repr(C, packed)]
+pub struct WSMT {
+    header: TableHeader,
+    protection_flags: U32,
+}
+
+crate::assert_same_size!(WSMT, [u8; 40]);
+
+impl WSMT {
+    pub fn new(oem_id: [u8; 6], oem_table_id: [u8; 8], oem_revision: u32) -> Self {
+        Self {
+            header: header(*b"WSMT", Self::len(), oem_id, oem_table_id, oem_revision),
+            ..Default::default()
+        }
+        .update_header()
+    }
+
+    fn update_header(mut self) -> Self {
+        self.header.checksum = 0;
+        self.header.checksum = crate::generate_checksum(self.as_bytes());
+        self
+    }
+
+    pub fn flag(mut self, flag: WsmtFlags) -> Self {
+        let mut flags = flag as u32;
+        if flag == WsmtFlags::CommBufferNestedPtrProtection {
+            flags |= WsmtFlags::FixedCommBuffers as u32;
+        }
+        self.protection_flags = (self.protection_flags.get() | flags).into();
+        self.update_header()
+    }
+
+    pub fn len() -> usize {
+        core::mem::size_of::<Self>()
+    }
+}
+
+crate::aml_as_bytes!(WSMT);
+
+/// Clockwise rotation applied to the boot image before it was drawn
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum Orientation {
+    #[default]
+    None = 0,
+    Rotated90 = 1,
+    Rotated180 = 2,
+    Rotated270 = 3,
+}
+
+/// The Boot Graphics Resource Table points to the bitmap that the
+/// firmware drew on the screen during boot, so that the OS can keep
+/// it displayed.
+#[derive(Copy, Clone, Default, IntoBytes, Immutable)]
+#[repr(C, packed)]
+pub struct BGRT {
+    header: TableHeader,
+    version: U16,
+    status: u8,
+    image_type: u8,
+    image_address: U64,
+    image_offset_x: U32,
+    image_offset_y: U32,
+}
+
+crate::assert_same_size!(BGRT, [u8; 56]);
+
+impl BGRT {
+    /// `image_address` is the address of the BMP image in memory,
+    /// drawn with its upper-left corner at (`offset_x`, `offset_y`)
+    pub fn new(
+        oem_id: [u8; 6],
+        oem_table_id: [u8; 8],
+        oem_revision: u32,
+        image_address: u64,
+        offset_x: u32,
+        offset_y: u32,
+    ) -> Self {
+        Self {
+            header: header(*b"BGRT", Self::len(), oem_id, oem_table_id, oem_revision),
+            version: 1.into(),
+            image_type: 0, // bitmap
+            image_address: image_address.into(),
+            image_offset_x: offset_x.into(),
+            image_offset_y: offset_y.into(),
+            ..Default::default()
+        }
+        .update_header()
+    }
+
+    fn update_header(mut self) -> Self {
+        self.header.checksum = 0;
+        self.header.checksum = crate::generate_checksum(self.as_bytes());
+        self
+    }
+
+    /// The image is still on the screen
+    pub fn displayed(mut self) -> Self {
+        self.status |= 1 << 0;
+        self.update_header()
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.status = (self.status & !(0b11 << 1)) | (orientation as u8) << 1;
+        self.update_header()
+    }
+
+    pub fn len() -> usize {
+        core::mem::size_of::<Self>()
+    }
+}
+
+crate::aml_as_bytes!(BGRT);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_table;
+
+    #[test]
+    fn test_waet() {
+        let waet = WAET::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef)
+            .flag(WaetFlags::RtcGood)
+            .flag(WaetFlags::PmTimerGood);
+        let bytes = check_table(&waet);
+        assert_eq!(bytes[0..4], *b"WAET");
+        assert_eq!(bytes[4], 40);
+        assert_eq!(bytes[36..], [3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_wsmt() {
+        let wsmt = WSMT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef)
+            .flag(WsmtFlags::FixedCommBuffers)
+            .flag(WsmtFlags::CommBufferNestedPtrProtection)
+            .flag(WsmtFlags::SystemResourceProtection);
+        let bytes = check_table(&wsmt);
+        assert_eq!(bytes[0..4], *b"WSMT");
+        assert_eq!(bytes[4], 40);
+        assert_eq!(bytes[36..], [7, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_wsmt_nested_pointers() {
+        let wsmt = WSMT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef)
+            .flag(WsmtFlags::CommBufferNestedPtrProtection);
+        let bytes = check_table(&wsmt);
+        assert_eq!(bytes[36..], [3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_bgrt() {
+        let bgrt = BGRT::new(
+            *b"FOOBAR",
+            *b"CAFEDEAD",
+            0xdead_beef,
+            0x7e00_0000,
+            0x200,
+            0x100,
+        )
+        .displayed()
+        .orientation(Orientation::Rotated270);
+        let bytes = check_table(&bgrt);
+        assert_eq!(bytes[0..4], *b"BGRT");
+        assert_eq!(bytes[4], 56);
+        assert_eq!(
+            bytes[36..],
+            [
+                1, 0, 7, 0, // version, status, image type
+                0x00, 0x00, 0x00, 0x7e, 0, 0, 0, 0, // image address
+                0x00, 0x02, 0, 0, 0x00, 0x01, 0, 0, // offsets
+            ]
+        );
+    }
+}
@@ -66,6 +66,10 @@ impl Aml for XSDT {
             sink.qword(*entry);
         }
     }
+
+    fn aml_len(&self) -> usize {
+        self.header.length.get() as usize
+    }
 }
 
 #[cfg(test)]
@@ -79,6 +83,7 @@ mod tests {
         let mut bytes = Vec::new();
         let xsdt: &dyn Aml = &XSDT::new(*b"FOOBAR", *b"CAFEDEAD", 0xdead_beef);
         xsdt.to_aml_bytes(&mut bytes);
+        assert_eq!(xsdt.aml_len(), bytes.len());
         let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
         assert_eq!(sum, 0);
     }
@@ -93,6 +98,7 @@ mod tests {
             xsdt.add_entry((i * 42) as u64);
 
             xsdt.to_aml_bytes(&mut bytes);
+            assert_eq!(xsdt.aml_len(), bytes.len());
             let sum = bytes.iter().fold(0u8, |acc, x| acc.wrapping_add(*x));
 
             let len = xsdt.len();